@@ -0,0 +1,465 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+	parse::{Parse, ParseStream, Parser},
+	punctuated::Punctuated,
+	spanned::Spanned,
+	Attribute,
+	Fields,
+	FnArg,
+	Ident,
+	ImplItem,
+	ItemEnum,
+	ItemImpl,
+	Pat,
+	Path,
+	ReturnType,
+	Signature,
+	Token,
+	Type,
+	Visibility,
+};
+
+use crate::generics::{impl_generics, type_generics};
+
+pub fn delegated_enum(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+	let traits = Punctuated::<Path, Token![,]>::parse_terminated.parse2(attr)?;
+
+	let ItemEnum {
+		attrs,
+		vis,
+		ident,
+		generics: enum_generics,
+		variants,
+		..
+	} = syn::parse2(item)?;
+
+	let generics = type_generics(&enum_generics)?;
+	let (kind, attrs) = kind_section(attrs, &vis)?;
+
+	let variants = variants
+		.iter()
+		.map(|variant| {
+			if let Some(attr) = variant.attrs.first() {
+				return Err(syn::Error::new(attr.span(), "attributes on variants are not supported"));
+			}
+
+			if let Some((_, discriminant)) = &variant.discriminant {
+				return Err(syn::Error::new(discriminant.span(), "discriminants are not supported"));
+			}
+
+			match &variant.fields {
+				Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+					Ok((&variant.ident, &fields.unnamed[0].ty))
+				}
+				_ => Err(syn::Error::new(variant.span(), "variants must have a single unnamed field: `Variant(Type)`")),
+			}
+		})
+		.collect::<syn::Result<Vec<_>>>()?;
+
+	let var_tys = variants.iter().map(|(_, var_ty)| var_ty).collect::<Vec<_>>();
+	let variants = variants
+		.iter()
+		.map(|(var_ident, var_ty)| quote! { #var_ident(#var_ty) })
+		.collect::<Vec<_>>();
+
+	// Traits registered with `delegatable_trait!`, delegated by name.
+	// Generic enums also need each variant type to implement the trait.
+	let (trait_gen, _) = impl_generics(&enum_generics);
+	let delegates = traits.iter().map(|trait_path| {
+		let mut bounds = enum_generics
+			.where_clause
+			.iter()
+			.flat_map(|where_clause| &where_clause.predicates)
+			.map(|predicate| quote! { #predicate })
+			.collect::<Vec<_>>();
+
+		if !enum_generics.params.is_empty() {
+			bounds.extend(var_tys.iter().map(|var_ty| quote! { #var_ty: #trait_path }));
+		}
+
+		let bounds = (!bounds.is_empty()).then(|| quote! { where [ #( #bounds ),* ] });
+		quote! { impl #trait_gen trait #trait_path #bounds; }
+	});
+
+	let registry = registry(&ident, &generics, &quote! { #( #variants ),* });
+
+	Ok(quote! {
+		::declarative_type_state::delegated_enum! {
+			ENUM_OUT: {
+				#( #attrs )*
+				#vis enum #ident #generics {
+					#( #variants ),*
+				}
+			}
+
+			DELEGATES: {
+				#( #delegates )*
+			}
+
+			#kind
+		}
+
+		#registry
+	})
+}
+
+/// Takes `#[kind(Ident)]` out of the enum attributes, as the `KIND` section.
+pub fn kind_section(attrs: Vec<Attribute>, vis: &Visibility) -> syn::Result<(Option<TokenStream>, Vec<Attribute>)> {
+	let (kind_attrs, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|attr| attr.path().is_ident("kind"));
+
+	let kind = match kind_attrs.as_slice() {
+		[] => None,
+		[kind] => {
+			let kind_ident: Ident = kind.parse_args()?;
+			Some(quote! { KIND: { #vis enum #kind_ident; } })
+		}
+		[_, duplicate, ..] => return Err(syn::Error::new(duplicate.span(), "duplicate `#[kind]` attribute")),
+	};
+
+	Ok((kind, attrs))
+}
+
+pub fn delegate(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+	// `#[delegate(skip(A, B))]`: variants that don't implement the trait, methods with a body fall back to it
+	// `#[delegate(via(field))]`: the trait is implemented by a field of each variant
+	let mut skip = None;
+	let mut via = None;
+
+	for arg in Punctuated::<syn::MetaList, Token![,]>::parse_terminated.parse2(attr)? {
+		let duplicate = if arg.path.is_ident("skip") {
+			skip.replace(arg.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?).is_some()
+		} else if arg.path.is_ident("via") {
+			via.replace(arg.parse_args_with(Punctuated::<syn::Member, Token![.]>::parse_separated_nonempty)?).is_some()
+		} else {
+			return Err(syn::Error::new(arg.path.span(), "expected `skip(Variant, ..)` or `via(field)`"));
+		};
+
+		if duplicate {
+			return Err(syn::Error::new(arg.path.span(), "duplicate argument"));
+		}
+	}
+
+	let item: ItemImpl = syn::parse2(item)?;
+
+	if let Some(unsafety) = &item.unsafety {
+		return Err(syn::Error::new(unsafety.span(), "unsafe impls are not supported"));
+	}
+
+	let enum_ident = match &*item.self_ty {
+		Type::Path(path) if path.qself.is_none() => &path.path.segments.last().unwrap().ident,
+		other => return Err(syn::Error::new(other.span(), "expected the enum type")),
+	};
+
+	let items = item
+		.items
+		.iter()
+		.map(|item| delegated_item(item, skip.is_some()))
+		.collect::<syn::Result<Vec<_>>>()?;
+
+	let delegates = match &item.trait_ {
+		Some((Some(bang), _, _)) => {
+			return Err(syn::Error::new(bang.span(), "negative impls are not supported"));
+		}
+		Some((None, trait_path, _)) => {
+			let (trait_gen, trait_bound) = impl_generics(&item.generics);
+			let skip = skip.map(|skip| quote! { [@skip #skip] });
+			let via = via.map(|via| {
+				let via = via.iter();
+				quote! { via #( . #via )* }
+			});
+
+			quote! {
+				#skip impl #trait_gen trait #trait_path #via #trait_bound {
+					#( #items )*
+				}
+			}
+		}
+		None if skip.is_some() || via.is_some() => {
+			return Err(syn::Error::new(item.self_ty.span(), "`skip` and `via` only apply to trait impls"));
+		}
+		// Generics of inherent impls are taken from the enum
+		None => quote! {
+			impl {
+				#( #items )*
+			}
+		},
+	};
+
+	let registry_ident = registry_ident(enum_ident);
+	Ok(quote! {
+		#registry_ident! { #delegates }
+	})
+}
+
+/// Name of the macro that forwards delegations to `enum_delegate_impls!`,
+/// it holds the enum definition so that `#[delegate]` can be used on separate `impl` blocks.
+pub fn registry_ident(enum_ident: &Ident) -> Ident { format_ident!("__delegate_{}", enum_ident) }
+
+pub fn registry(enum_ident: &Ident, generics: &TokenStream, variants: &TokenStream) -> TokenStream {
+	let registry_ident = registry_ident(enum_ident);
+
+	quote! {
+		#[doc(hidden)]
+		#[allow(unused_macros)]
+		macro_rules! #registry_ident {
+			( $( $delegates: tt )* ) => {
+				::declarative_type_state::enum_delegate_impls! {
+					ENUM_IN: {
+						#enum_ident #generics {
+							#variants
+						}
+					}
+
+					DELEGATES: { $( $delegates )* }
+				}
+			};
+		}
+	}
+}
+
+/// A method declared without a body: `pub fn name(&self) -> Type;`
+struct DelegatedFn {
+	attrs: Vec<Attribute>,
+	vis:   Visibility,
+	sig:   Signature,
+}
+
+impl Parse for DelegatedFn {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let vis = input.parse()?;
+		let sig = input.parse()?;
+		input.parse::<Token![;]>()?;
+		Ok(Self { attrs, vis, sig })
+	}
+}
+
+/// A constant declared without a value, aggregated across the variants: `#[max] const NAME: Type;`
+struct DelegatedConst {
+	attrs: Vec<Attribute>,
+	vis:   Visibility,
+	ident: Ident,
+	ty:    Type,
+}
+
+impl Parse for DelegatedConst {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let vis = input.parse()?;
+		input.parse::<Token![const]>()?;
+		let ident = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let ty = input.parse()?;
+		input.parse::<Token![;]>()?;
+		Ok(Self { attrs, vis, ident, ty })
+	}
+}
+
+fn delegated_item(item: &ImplItem, fallbacks: bool) -> syn::Result<TokenStream> {
+	match item {
+		ImplItem::Const(constant) => {
+			reject_attrs(&constant.attrs)?;
+			let ident = &constant.ident;
+			let ty = &constant.ty;
+			let expr = &constant.expr;
+			Ok(quote! { [const #ident: #ty = #expr] })
+		}
+		ImplItem::Type(ty) => {
+			reject_attrs(&ty.attrs)?;
+			let ident = &ty.ident;
+			let generics = &ty.generics;
+			let assigned = &ty.ty;
+			Ok(quote! { [type #ident #generics = #assigned] })
+		}
+		// The body is the fallback of the skipped variants
+		ImplItem::Fn(method) if fallbacks => {
+			let method_fn = DelegatedFn {
+				attrs: method.attrs.clone(),
+				vis:   method.vis.clone(),
+				sig:   method.sig.clone(),
+			};
+			delegated_fn(method_fn, Some(&method.block))
+		}
+		ImplItem::Fn(method) => {
+			Err(syn::Error::new(
+				method.block.span(),
+				"delegated methods must not have a body, replace it with `;`",
+			))
+		}
+		ImplItem::Verbatim(tokens) => match syn::parse2(tokens.clone()) {
+			Ok(constant) => delegated_const(constant),
+			Err(_) => delegated_fn(syn::parse2(tokens.clone())?, None),
+		},
+		other => Err(syn::Error::new(other.span(), "unsupported item")),
+	}
+}
+
+fn delegated_const(DelegatedConst { attrs, vis, ident, ty }: DelegatedConst) -> syn::Result<TokenStream> {
+	let (aggregates, attrs): (Vec<_>, Vec<_>) = attrs
+		.into_iter()
+		.partition(|attr| ["max", "min", "sum"].iter().any(|aggregate| attr.path().is_ident(aggregate)));
+	reject_attrs(&attrs)?;
+
+	let aggregate = match aggregates.as_slice() {
+		[aggregate] => aggregate.path().get_ident(),
+		[_, duplicate, ..] => return Err(syn::Error::new(duplicate.span(), "duplicate aggregate attribute")),
+		[] => {
+			return Err(syn::Error::new(
+				ident.span(),
+				"constants without a value need an aggregate: `#[max]`, `#[min]` or `#[sum]`",
+			));
+		}
+	};
+
+	Ok(quote! { [[@#aggregate] #vis const #ident: #ty] })
+}
+
+fn delegated_fn(DelegatedFn { attrs, vis, sig }: DelegatedFn, fallback: Option<&syn::Block>) -> syn::Result<TokenStream> {
+	let (wrap, attrs) = take_attr(attrs, "wrap")?;
+	let (default, attrs) = take_attr(attrs, "default")?;
+	reject_attrs(&attrs)?;
+
+	// `#[default(Variant)]` delegates a function without receiver to the type of that variant
+	let default = default
+		.map(|default| default.parse_args::<Ident>().map(|var_ident| quote! { [@default #var_ident] }))
+		.transpose()?;
+
+	if let Some(abi) = &sig.abi {
+		return Err(syn::Error::new(abi.span(), "extern functions are not supported"));
+	}
+
+	if let Some(variadic) = &sig.variadic {
+		return Err(syn::Error::new(variadic.span(), "variadic functions are not supported"));
+	}
+
+	let qualifiers = [
+		sig.constness.map(|constness| quote! { #constness }),
+		sig.asyncness.map(|asyncness| quote! { #asyncness }),
+		sig.unsafety.map(|unsafety| quote! { #unsafety }),
+	]
+	.into_iter()
+	.flatten()
+	.collect::<Vec<_>>();
+
+	let qualifiers = (!qualifiers.is_empty()).then(|| quote! { [ #( #qualifiers )* ] });
+
+	let mut inputs = sig.inputs.iter().peekable();
+
+	let receiver = match inputs.next_if(|input| matches!(input, FnArg::Receiver(_))) {
+		Some(receiver) if default.is_some() => {
+			return Err(syn::Error::new(receiver.span(), "functions delegated to a variant take no receiver"));
+		}
+		None if default.is_some() => None,
+		Some(FnArg::Receiver(receiver)) if receiver.colon_token.is_some() => Some(typed_receiver(&receiver.ty)?),
+		Some(FnArg::Receiver(receiver)) => {
+			match &receiver.reference {
+				Some((_, Some(lifetime))) => {
+					return Err(syn::Error::new(lifetime.span(), "receivers with lifetimes are not supported"));
+				}
+				Some((_, None)) if receiver.mutability.is_some() => Some(quote! { &mut self }),
+				Some((_, None)) => Some(quote! { &self }),
+				None if receiver.mutability.is_some() => {
+					return Err(syn::Error::new(receiver.span(), "`mut self` is not supported, use `self`"));
+				}
+				None => Some(quote! { self }),
+			}
+		}
+		_ => {
+			return Err(syn::Error::new(
+				sig.inputs.span(),
+				"delegated methods need a receiver: `self`, `&self`, `&mut self` or a typed receiver such as `self: Box<Self>`, \
+				 or `#[default(Variant)]` to delegate to the type of a variant",
+			));
+		}
+	};
+
+	let args = inputs
+		.map(|arg| {
+			let FnArg::Typed(arg) = arg
+			else { unreachable!("receiver can only be the first argument") };
+
+			match &*arg.pat {
+				Pat::Ident(pat) if pat.by_ref.is_none() && pat.mutability.is_none() && pat.subpat.is_none() => {
+					let ident = &pat.ident;
+					let ty = &arg.ty;
+					Ok(quote! { #ident: #ty })
+				}
+				other => Err(syn::Error::new(other.span(), "arguments must be plain identifiers")),
+			}
+		})
+		.collect::<syn::Result<Vec<_>>>()?;
+
+	let params = receiver.into_iter().chain(args);
+	let fn_ident = &sig.ident;
+	let (gens, bounds) = impl_generics(&sig.generics);
+	// `#[wrap]` converts the return of each variant into the enum, as `-> [Self]`
+	let output = match (&sig.output, &wrap) {
+		(ReturnType::Default, None) => None,
+		(ReturnType::Default, Some(wrap)) => {
+			return Err(syn::Error::new(wrap.span(), "`#[wrap]` needs a return type: `Self` or `Transition<Self, Next>`"));
+		}
+		(ReturnType::Type(_, ty), None) => Some(quote! { -> #ty }),
+		(ReturnType::Type(_, ty), Some(_)) => Some(quote! { -> [ #ty ] }),
+	};
+
+	let fallback = fallback.map(|fallback| quote! { else #fallback });
+
+	Ok(quote! {
+		[ #default #vis #qualifiers fn #fn_ident #gens ( #( #params ),* ) #output #bounds #fallback ]
+	})
+}
+
+/// The declarative syntax matches typed receivers literally, the wrapper must be named without a path.
+fn typed_receiver(ty: &Type) -> syn::Result<TokenStream> {
+	let error = || {
+		syn::Error::new(
+			ty.span(),
+			"supported typed receivers: `self: Box<Self>`, `self: Pin<&mut Self>`, `self: Rc<Self>` or `self: Arc<Self>`",
+		)
+	};
+
+	let Type::Path(path) = ty
+	else { return Err(error()) };
+
+	let Some(segment) = path.path.segments.first().filter(|_| path.qself.is_none() && path.path.segments.len() == 1)
+	else { return Err(error()) };
+
+	let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+	else { return Err(error()) };
+
+	let [syn::GenericArgument::Type(arg)] = args.args.iter().collect::<Vec<_>>()[..]
+	else { return Err(error()) };
+
+	let is_self = |ty: &Type| matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"));
+
+	match (segment.ident.to_string().as_str(), arg) {
+		("Box", arg) if is_self(arg) => Ok(quote! { self: Box<Self> }),
+		("Rc", arg) if is_self(arg) => Ok(quote! { self: Rc<Self> }),
+		("Arc", arg) if is_self(arg) => Ok(quote! { self: Arc<Self> }),
+		("Pin", Type::Reference(reference))
+			if reference.lifetime.is_none() && reference.mutability.is_some() && is_self(&reference.elem) =>
+		{
+			Ok(quote! { self: Pin<&mut Self> })
+		}
+		_ => Err(error()),
+	}
+}
+
+/// Takes the attribute `name` out of the item attributes, it may appear at most once.
+fn take_attr(attrs: Vec<Attribute>, name: &str) -> syn::Result<(Option<Attribute>, Vec<Attribute>)> {
+	let (taken, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|attr| attr.path().is_ident(name));
+
+	match taken.as_slice() {
+		[_, duplicate, ..] => Err(syn::Error::new(duplicate.span(), format!("duplicate `#[{name}]` attribute"))),
+		_ => Ok((taken.into_iter().next(), attrs)),
+	}
+}
+
+/// The declarative syntax has no place for attributes on delegated items, doc comments are ignored.
+fn reject_attrs(attrs: &[Attribute]) -> syn::Result<()> {
+	match attrs.iter().find(|attr| !attr.path().is_ident("doc")) {
+		Some(attr) => Err(syn::Error::new(attr.span(), "attributes on delegated items are not supported")),
+		None => Ok(()),
+	}
+}
@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, GenericParam, Generics};
+
+/// Splits the generics of a type definition into the bracketed forms used by the declarative macros:
+/// `<[ 'a, T ]> where [ T: Clone ]`.
+///
+/// Bounds declared inline are moved to the where clause, since the declarative macros
+/// use the generic parameters both as declarations and as arguments.
+pub fn type_generics(generics: &Generics) -> syn::Result<TokenStream> {
+	let mut params = Vec::new();
+	let mut bounds = Vec::new();
+
+	for param in &generics.params {
+		match param {
+			GenericParam::Lifetime(lifetime) => {
+				let ident = &lifetime.lifetime;
+				params.push(quote! { #ident });
+
+				if !lifetime.bounds.is_empty() {
+					let lifetime_bounds = &lifetime.bounds;
+					bounds.push(quote! { #ident: #lifetime_bounds });
+				}
+			}
+			GenericParam::Type(ty) => {
+				if let Some(default) = &ty.default {
+					return Err(syn::Error::new(default.span(), "default generic types are not supported"));
+				}
+
+				let ident = &ty.ident;
+				params.push(quote! { #ident });
+
+				if !ty.bounds.is_empty() {
+					let ty_bounds = &ty.bounds;
+					bounds.push(quote! { #ident: #ty_bounds });
+				}
+			}
+			GenericParam::Const(constant) => {
+				return Err(syn::Error::new(constant.span(), "const generics are not supported"));
+			}
+		}
+	}
+
+	if let Some(where_clause) = &generics.where_clause {
+		bounds.extend(where_clause.predicates.iter().map(|predicate| quote! { #predicate }));
+	}
+
+	let params = (!params.is_empty()).then(|| quote! { <[ #( #params ),* ]> });
+	let bounds = (!bounds.is_empty()).then(|| quote! { where [ #( #bounds ),* ] });
+	Ok(quote! { #params #bounds })
+}
+
+/// Names of the generic parameters, without their bounds: `'a, T`.
+pub fn param_names(generics: &Generics) -> Vec<TokenStream> {
+	generics
+		.params
+		.iter()
+		.map(|param| match param {
+			GenericParam::Lifetime(lifetime) => {
+				let ident = &lifetime.lifetime;
+				quote! { #ident }
+			}
+			GenericParam::Type(ty) => {
+				let ident = &ty.ident;
+				quote! { #ident }
+			}
+			GenericParam::Const(constant) => {
+				let ident = &constant.ident;
+				quote! { #ident }
+			}
+		})
+		.collect()
+}
+
+/// Generics of an `impl` block or method, the declarative macros accept these with inline bounds.
+///
+/// Returns the parameters and the where clause separately, since they are not adjacent in the
+/// declarative syntax: `impl <[ 'a, T: Clone ]> trait Trait where [ T: Copy ] { .. }`
+pub fn impl_generics(generics: &Generics) -> (TokenStream, TokenStream) {
+	let params = &generics.params;
+	let params = (!params.is_empty()).then(|| quote! { <[ #params ]> });
+	let bounds = generics
+		.where_clause
+		.as_ref()
+		.filter(|where_clause| !where_clause.predicates.is_empty())
+		.map(|where_clause| {
+			let predicates = &where_clause.predicates;
+			quote! { where [ #predicates ] }
+		});
+
+	(quote! { #params }, quote! { #bounds })
+}
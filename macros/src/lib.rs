@@ -0,0 +1,78 @@
+//! Attribute macros front end for [declarative_type_state](https://docs.rs/declarative_type_state).
+//!
+//! Each attribute parses regular Rust items and expands into an invocation of the
+//! equivalent declarative macro, so the generated code is the same as writing the macro by hand.
+//!
+//! Use through the `attr` module of `declarative_type_state` (feature `macros`).
+
+use proc_macro::TokenStream;
+
+mod delegate;
+mod generics;
+mod type_state;
+
+/// Generates the same output as `delegated_enum!`, from a regular enum definition.
+///
+/// Each variant must have a single unnamed field: `Variant(Type)`.
+///
+/// The arguments are traits registered with `delegatable_trait!`, which are delegated by name:
+/// `#[delegated_enum(Tick, Get<i64>)]`.
+/// Other traits and methods are delegated with [macro@delegate].
+///
+/// `#[kind(Ident)]` on the enum generates its fieldless kind `Ident`, same as the `KIND` section.
+#[proc_macro_attribute]
+pub fn delegated_enum(attr: TokenStream, item: TokenStream) -> TokenStream {
+	delegate::delegated_enum(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Delegates the methods of an `impl` block to the variants of an enum annotated with
+/// [macro@delegated_enum] or [macro@type_state_enum].
+///
+/// Methods are declared without a body, the same way as in a trait definition.
+/// The `impl` block must be placed after the enum, in the same module or in a child module.
+///
+/// `#[wrap]` on a method returning `Self` or `Transition<Self, Next>` converts the return of each variant into the enum.
+/// `#[default(Variant)]` on a function without receiver delegates it to the type of that variant.
+/// `#[max]`, `#[min]` or `#[sum]` on a constant without a value aggregates the constant of each variant's type.
+///
+/// `#[delegate(skip(A, B))]` opts the variants `A` and `B` out of a trait impl,
+/// methods written with a body use it as the fallback for those variants.
+/// `#[delegate(via(state))]` delegates a trait impl to the field `state` of each variant.
+#[proc_macro_attribute]
+pub fn delegate(attr: TokenStream, item: TokenStream) -> TokenStream {
+	delegate::delegate(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Marks a struct as the state struct of a type-state enum, see [macro@type_state_enum].
+///
+/// The argument names the field that holds the state: `#[type_state(state = "state")]`,
+/// the struct must be generic over the type of that field, as its last generic parameter.
+/// The other generic parameters become the generics of the enum.
+#[proc_macro_attribute]
+pub fn type_state(attr: TokenStream, item: TokenStream) -> TokenStream {
+	type_state::type_state(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Generates the same output as `type_state_enum!`, from a regular enum definition.
+///
+/// The argument is the state struct, which must be annotated with [macro@type_state]:
+/// `#[type_state_enum(Villager)]`.
+///
+/// - `#[vars(...)]` on the enum: attributes applied to every extracted variant.
+/// - `#[skip]` on a variant: does not extract the variant, same as `[@SKIP]`.
+/// - `#[transitions(A, B)]` on a variant: declares the states it may transition to, same as `TRANSITIONS`.
+/// - `#[on_enter]` / `#[on_exit]` on a variant: the state overrides that hook, same as `HOOKS`.
+/// - `#[tracked(Ident)]` on the enum: generates the tracked wrapper `Ident`, same as `TRACKED`.
+/// - `#[kind(Ident)]` on the enum: generates the fieldless kind `Ident`, same as `KIND`.
+#[proc_macro_attribute]
+pub fn type_state_enum(attr: TokenStream, item: TokenStream) -> TokenStream {
+	type_state::type_state_enum(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
@@ -0,0 +1,223 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+	punctuated::Punctuated,
+	spanned::Spanned,
+	Fields,
+	GenericParam,
+	Ident,
+	ItemEnum,
+	ItemStruct,
+	LitStr,
+	Path,
+	PredicateType,
+	Token,
+	Type,
+	WherePredicate,
+};
+
+use crate::{
+	delegate::{kind_section, registry},
+	generics::{param_names, type_generics},
+};
+
+pub fn type_state(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+	let mut state_field: Option<LitStr> = None;
+
+	let parser = syn::meta::parser(|meta| {
+		if meta.path.is_ident("state") {
+			state_field = Some(meta.value()?.parse()?);
+			Ok(())
+		} else {
+			Err(meta.error("unsupported argument, expected `state = \"field_name\"`"))
+		}
+	});
+
+	syn::parse::Parser::parse2(parser, attr.clone())?;
+
+	let Some(state_field) = state_field
+	else { return Err(syn::Error::new(attr.span(), "missing argument: `state = \"field_name\"`")) };
+
+	let item: ItemStruct = syn::parse2(item)?;
+
+	let Fields::Named(fields) = &item.fields
+	else { return Err(syn::Error::new(item.fields.span(), "the state struct must have named fields")) };
+
+	// The state type is the last parameter, the others are forwarded as the generics of the enum.
+	let mut generics = item.generics.clone();
+	let state_ty = match generics.params.pop().map(|pair| pair.into_value()) {
+		Some(GenericParam::Type(state_ty)) => state_ty.ident,
+		_ => {
+			return Err(syn::Error::new(
+				item.generics.span(),
+				"the state struct must have a generic type parameter as its last parameter: the state type",
+			));
+		}
+	};
+
+	if let Some(where_clause) = &mut generics.where_clause {
+		// Bounds on the state type only apply to the struct definition.
+		where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+			.into_iter()
+			.filter(|predicate| {
+				!matches!(predicate, WherePredicate::Type(PredicateType { bounded_ty: Type::Path(path), .. })
+					if path.qself.is_none() && path.path.is_ident(&state_ty))
+			})
+			.collect();
+	}
+
+	let param_names = param_names(&generics);
+	let generics = type_generics(&generics)?;
+
+	let state_ident = &item.ident;
+	let state_field = Ident::new(&state_field.value(), state_field.span());
+
+	if !fields.named.iter().any(|field| field.ident.as_ref() == Some(&state_field)) {
+		return Err(syn::Error::new(state_field.span(), format!("no field named `{state_field}`")));
+	}
+
+	let other_fields = fields
+		.named
+		.iter()
+		.filter_map(|field| field.ident.as_ref())
+		.filter(|field_ident| **field_ident != state_field);
+
+	let registry_ident = registry_ident(state_ident);
+	let expected = param_names.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+
+	Ok(quote! {
+		#item
+
+		#[doc(hidden)]
+		#[allow(unused_macros)]
+		macro_rules! #registry_ident {
+			( [ #( #param_names ),* ] { $( $enum_out: tt )* } $( $rest: tt )* ) => {
+				::declarative_type_state::type_state_enum! {
+					STATE: #state_ident #generics { #state_field #(, #other_fields )* }
+
+					ENUM_OUT: { $( $enum_out )* }
+
+					DELEGATES: {}
+
+					$( $rest )*
+				}
+			};
+
+			( [ $( $other: tt )* ] $( $rest: tt )* ) => {
+				compile_error!(concat!(
+					"the generics of the enum must match the ones of `", stringify!(#state_ident),
+					"` without the state type: `<", #expected, ">`",
+				));
+			};
+		}
+	})
+}
+
+pub fn type_state_enum(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+	let state_path: Path = syn::parse2(attr)?;
+	let state_ident = &state_path.segments.last().unwrap().ident;
+
+	let ItemEnum {
+		attrs,
+		vis,
+		ident,
+		generics,
+		variants,
+		..
+	} = syn::parse2(item)?;
+
+	let param_names = param_names(&generics);
+	let enum_generics = type_generics(&generics)?;
+
+	let (tracked_attrs, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|attr| attr.path().is_ident("tracked"));
+	let (kind, attrs) = kind_section(attrs, &vis)?;
+	let (vars_attrs, enum_attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|attr| attr.path().is_ident("vars"));
+
+	let all_meta = match vars_attrs.as_slice() {
+		[] => quote! {},
+		[vars] => vars.meta.require_list()?.tokens.clone(),
+		[_, duplicate, ..] => return Err(syn::Error::new(duplicate.span(), "duplicate `#[vars]` attribute")),
+	};
+
+	let mut var_defs = Vec::new();
+	let mut var_idents = Vec::new();
+	let mut transitions = Vec::new();
+	let mut on_enter = Vec::new();
+	let mut on_exit = Vec::new();
+
+	for variant in variants {
+		if let Some((_, discriminant)) = &variant.discriminant {
+			return Err(syn::Error::new(discriminant.span(), "discriminants are not supported"));
+		}
+
+		let mut skip = None;
+		let mut var_attrs = Vec::new();
+
+		for attr in variant.attrs {
+			if attr.path().is_ident("skip") {
+				attr.meta.require_path_only()?;
+				skip = Some(quote! { [@SKIP] });
+			} else if attr.path().is_ident("transitions") {
+				let targets = attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+				let targets = targets.iter();
+				let from = &variant.ident;
+				transitions.push(quote! { #from => [ #( #targets ),* ] });
+			} else if attr.path().is_ident("on_enter") {
+				attr.meta.require_path_only()?;
+				on_enter.push(variant.ident.clone());
+			} else if attr.path().is_ident("on_exit") {
+				attr.meta.require_path_only()?;
+				on_exit.push(variant.ident.clone());
+			} else {
+				var_attrs.push(attr);
+			}
+		}
+
+		let var_ident = &variant.ident;
+		let fields = &variant.fields;
+		var_defs.push(quote! { #skip #( #var_attrs )* #var_ident #fields });
+		var_idents.push(var_ident.clone());
+	}
+
+	let hooks = (!on_enter.is_empty() || !on_exit.is_empty())
+		.then(|| quote! { HOOKS: { OnEnter: [ #( #on_enter ),* ], OnExit: [ #( #on_exit ),* ] } });
+	let transitions = (!transitions.is_empty()).then(|| quote! { TRANSITIONS: { #( #transitions ),* } });
+
+	let tracked = match tracked_attrs.as_slice() {
+		[] => None,
+		[tracked] => {
+			let tracked_ident: Ident = tracked.parse_args()?;
+			Some(quote! { TRACKED: { #vis struct #tracked_ident; } })
+		}
+		[_, duplicate, ..] => return Err(syn::Error::new(duplicate.span(), "duplicate `#[tracked]` attribute")),
+	};
+
+	let state_registry = registry_ident(state_ident);
+	let delegate_vars = var_idents
+		.iter()
+		.map(|var_ident| quote! { #var_ident(#state_path<#( #param_names, )* #var_ident>) });
+	let delegate_registry = registry(&ident, &enum_generics, &quote! { #( #delegate_vars ),* });
+
+	Ok(quote! {
+		#state_registry! {
+			[ #( #param_names ),* ]
+			{
+				#[vars( #all_meta )]
+				#( #enum_attrs )*
+				#vis enum #ident {
+					#( #var_defs ),*
+				}
+			}
+
+			#hooks
+			#transitions
+			#tracked
+			#kind
+		}
+
+		#delegate_registry
+	})
+}
+
+/// Name of the macro that holds the state struct definition, used by `#[type_state_enum]`.
+fn registry_ident(state_ident: &Ident) -> Ident { format_ident!("__type_state_{}", state_ident) }
@@ -0,0 +1,470 @@
+//! Attribute macros, an alternative syntax for [delegated_enum!](crate::delegated_enum) and
+//! [type_state_enum!](crate::type_state_enum) that works with rustfmt and reports errors on the offending tokens.
+//!
+//! Each attribute expands into the equivalent declarative macro invocation, both syntaxes can be mixed in the same crate.
+//!
+//! Requires the feature `macros`.
+//!
+//! # Delegated enums
+//! ```
+//! use declarative_type_state::attr::{delegate, delegated_enum};
+//!
+//! trait Tick {
+//! 	fn tick(&mut self, delta_time: f64);
+//! }
+//!
+//! #[delegated_enum]
+//! #[derive(Debug)]
+//! enum Timer {
+//! 	Int(i32),
+//! 	Float(f64),
+//! }
+//!
+//! #[delegate]
+//! impl Tick for Timer {
+//! 	fn tick(&mut self, delta_time: f64);
+//! }
+//!
+//! impl Tick for i32 {
+//! 	fn tick(&mut self, delta_time: f64) { *self += delta_time as i32; }
+//! }
+//!
+//! impl Tick for f64 {
+//! 	fn tick(&mut self, delta_time: f64) { *self += delta_time; }
+//! }
+//!
+//! let mut timer = Timer::Float(1.0);
+//! timer.tick(0.5);
+//! assert!(matches!(timer, Timer::Float(1.5)));
+//! ```
+//!
+//! `#[delegate]` must be placed after the enum definition, because it expands into a macro generated by `#[delegated_enum]`.
+//!
+//! Traits registered with [delegatable_trait!](crate::delegatable_trait) can be delegated by name instead:
+//! `#[delegated_enum(Tick)]`.
+//!
+//! `#[kind(Ident)]` on the enum generates its fieldless kind, same as the `KIND` section of `delegated_enum!`.
+//!
+//! `#[wrap]` on a delegated method converts the return of each variant into the enum,
+//! same as writing the return type inside brackets: `-> [Self]`.
+//!
+//! `#[default(Variant)]` on a function without receiver delegates it to the type of that variant,
+//! and `#[max]`, `#[min]` or `#[sum]` on a constant without a value aggregates the constant of each variant's type.
+//!
+//! `#[delegate(skip(A, B))]` opts the variants `A` and `B` out of a trait,
+//! the methods written with a body use it as the fallback for those variants.
+//!
+//! `#[delegate(via(state))]` delegates a trait to the field `state` of each variant,
+//! e.g. to the state of a type-state enum: the trait only needs to be implemented by the state types.
+//!
+//! # Type-state enums
+//! ```
+//! use declarative_type_state::attr::{type_state, type_state_enum};
+//!
+//! #[type_state(state = "state")]
+//! #[derive(Debug)]
+//! pub struct Villager<S: ?Sized> {
+//! 	hunger: f32,
+//! 	state:  S,
+//! }
+//!
+//! #[type_state_enum(Villager)]
+//! #[vars(derive(Debug))]
+//! #[derive(Debug)]
+//! pub enum VillagerEnum {
+//! 	#[transitions(Hungry)]
+//! 	Idle,
+//! 	#[transitions(Idle)]
+//! 	Hungry { since: f32 },
+//! }
+//! ```
+//!
+//! The state struct must be generic over the type of its state field, with `?Sized` allowed,
+//! and the state field must be the last one.
+//! The type of the state field must also be the last generic parameter, the others become the generics of the enum:
+//! `Villager<'w, W: World, S: ?Sized>` goes with `enum VillagerEnum<'w, W: World>`, the names must match.
+//!
+//! Attributes of the type-state enum:
+//! - `#[vars(...)]` on the enum: attributes applied to every extracted variant, same as `#[vars(...)]` in `type_state_enum!`.
+//! - `#[skip]` on a variant: the variant is not extracted, same as `[@SKIP]`.
+//! - `#[transitions(A, B)]` on a variant: the states it may transition to, same as the `TRANSITIONS` section.
+//! - `#[on_enter]` / `#[on_exit]` on a variant: the state implements that hook, same as the `HOOKS` section.
+//! - `#[tracked(Ident)]` on the enum: generates the tracked wrapper `Ident`, same as the `TRACKED` section.
+//! - `#[kind(Ident)]` on the enum: generates the fieldless kind `Ident`, same as the `KIND` section.
+//!
+//! These attributes are consumed by `#[type_state_enum]`, which must come before them.
+
+pub use declarative_type_state_macros::{delegate, delegated_enum, type_state, type_state_enum};
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_delegated_enum {
+	use super::{delegate, delegated_enum};
+	use std::rc::Rc;
+
+	trait Tick {
+		fn tick(&mut self, delta_time: f64);
+
+		fn name(&self) -> &'static str;
+	}
+
+	trait Get<T> {
+		fn get(&self) -> T;
+	}
+
+	#[delegated_enum]
+	#[derive(Clone, Debug)]
+	enum Timer<'a, S: Copy> {
+		Int(i32),
+		Slice(&'a [S]),
+	}
+
+	#[delegate]
+	impl<'a, S: Copy> Tick for Timer<'a, S>
+	where &'a [S]: Tick
+	{
+		fn tick(&mut self, delta_time: f64);
+
+		fn name(&self) -> &'static str;
+	}
+
+	#[delegate]
+	impl<'a, S: Copy> Get<i64> for Timer<'a, S>
+	where &'a [S]: Get<i64>
+	{
+		fn get(&self) -> i64;
+	}
+
+	impl Tick for i32 {
+		fn tick(&mut self, delta_time: f64) { *self += delta_time as i32; }
+
+		fn name(&self) -> &'static str { "i32" }
+	}
+
+	impl Tick for &[u8] {
+		fn tick(&mut self, _delta_time: f64) { *self = &self[1..]; }
+
+		fn name(&self) -> &'static str { "&[u8]" }
+	}
+
+	impl Get<i64> for i32 {
+		fn get(&self) -> i64 { *self as i64 }
+	}
+
+	impl Get<i64> for &[u8] {
+		fn get(&self) -> i64 { self.len() as i64 }
+	}
+
+	#[delegated_enum]
+	#[kind(NumberKind)]
+	#[derive(Clone)]
+	enum Number {
+		Int(i32),
+		Byte(u8),
+	}
+
+	#[delegate]
+	impl Number {
+		pub const fn count_ones(self) -> u32;
+
+		#[max]
+		pub const BITS: u32;
+	}
+
+	#[delegate]
+	impl Default for Number {
+		#[default(Byte)]
+		fn default() -> Self;
+	}
+
+	trait Halve {
+		fn halve(&mut self) -> bool;
+	}
+
+	impl Halve for i32 {
+		fn halve(&mut self) -> bool {
+			*self /= 2;
+			true
+		}
+	}
+
+	#[delegate(skip(Byte))]
+	impl Halve for Number {
+		fn halve(&mut self) -> bool { false }
+	}
+
+	trait Unbox {
+		fn unbox(self: Box<Self>) -> i64;
+	}
+
+	impl Unbox for i32 {
+		fn unbox(self: Box<Self>) -> i64 { *self as i64 }
+	}
+
+	impl Unbox for u8 {
+		fn unbox(self: Box<Self>) -> i64 { *self as i64 }
+	}
+
+	#[delegate]
+	impl Unbox for Number {
+		fn unbox(self: Box<Self>) -> i64;
+	}
+
+	trait Owners {
+		fn owners(self: Rc<Self>) -> usize;
+	}
+
+	impl Owners for i32 {
+		fn owners(self: Rc<Self>) -> usize { Rc::strong_count(&self) }
+	}
+
+	impl Owners for u8 {
+		fn owners(self: Rc<Self>) -> usize { Rc::strong_count(&self) }
+	}
+
+	#[delegate]
+	impl Owners for Number {
+		fn owners(self: Rc<Self>) -> usize;
+	}
+
+	trait Double {
+		fn double(&self) -> Self;
+	}
+
+	impl Double for i32 {
+		fn double(&self) -> Self { self * 2 }
+	}
+
+	impl Double for u8 {
+		fn double(&self) -> Self { self.saturating_mul(2) }
+	}
+
+	#[delegate]
+	impl Double for Number {
+		#[wrap]
+		fn double(&self) -> Self;
+	}
+
+	crate::delegatable_trait! {
+		trait Describe {
+			[fn describe(&self, prefix: &str) -> String]
+		}
+	}
+
+	impl Describe for i32 {
+		fn describe(&self, prefix: &str) -> String { format!("{prefix}{self}") }
+	}
+
+	impl Describe for &[u8] {
+		fn describe(&self, prefix: &str) -> String { format!("{prefix}{self:?}") }
+	}
+
+	#[delegated_enum(Describe)]
+	enum Described<'a> {
+		Int(i32),
+		Slice(&'a [u8]),
+	}
+
+	#[test]
+	fn plain_delegation() {
+		let mut timer = Timer::<u8>::Int(5);
+		timer.tick(2.0);
+		assert_eq!(timer.name(), "i32");
+		assert_eq!(timer.get(), 7);
+
+		let bytes = [1, 2, 3];
+		let mut timer = Timer::Slice(bytes.as_slice());
+		timer.tick(2.0);
+		assert_eq!(timer.name(), "&[u8]");
+		assert_eq!(timer.get(), 2);
+
+		assert_eq!(Number::Int(7).count_ones(), 3);
+		assert_eq!(Number::Byte(3).count_ones(), 2);
+	}
+
+	#[test]
+	fn delegatable_trait() {
+		assert_eq!(Described::Int(3).describe("int: "), "int: 3");
+		assert_eq!(Described::Slice(&[1, 2]).describe("slice: "), "slice: [1, 2]");
+	}
+
+	#[test]
+	fn kind() {
+		assert_eq!(Number::Byte(3).kind(), NumberKind::Byte);
+		assert_eq!(Number::Int(3).kind(), NumberKind::Int);
+	}
+
+	#[test]
+	fn box_receiver() {
+		assert_eq!(Box::new(Number::Int(-2)).unbox(), -2);
+	}
+
+	// The shared enum is cloned, the variant is called through a new pointer
+	#[test]
+	fn rc_receiver() {
+		assert_eq!(Rc::new(Number::Int(3)).owners(), 1);
+
+		let shared = Rc::new(Number::Byte(1));
+		let other = Rc::clone(&shared);
+		assert_eq!(shared.owners(), 1);
+		assert!(matches!(*other, Number::Byte(1)));
+	}
+
+	#[test]
+	fn wrap() {
+		assert!(matches!(Number::Byte(200).double(), Number::Byte(255)));
+		assert!(matches!(Number::Int(-3).double(), Number::Int(-6)));
+	}
+
+	#[test]
+	fn default_and_max() {
+		assert!(matches!(Number::default(), Number::Byte(0)));
+		assert_eq!(Number::BITS, 32);
+	}
+
+	#[test]
+	fn skip() {
+		assert!(Number::Int(4).halve());
+		assert!(!Number::Byte(4).halve());
+	}
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_type_state_enum {
+	use super::{delegate, type_state, type_state_enum};
+	use crate::Transition;
+
+	pub trait World {
+		fn danger(&self) -> f32;
+	}
+
+	pub struct Village;
+
+	impl World for Village {
+		fn danger(&self) -> f32 { 0.9 }
+	}
+
+	#[type_state(state = "state")]
+	pub struct Villager<'w, W: World, S: ?Sized> {
+		world:  &'w W,
+		hunger: f32,
+		state:  S,
+	}
+
+	#[type_state_enum(Villager)]
+	#[vars(derive(Debug))]
+	#[tracked(VillagerTracked)]
+	#[kind(VillagerKind)]
+	pub enum VillagerEnum<'w, W: World> {
+		#[transitions(Hungry, Fleeing)]
+		Idle,
+		#[transitions(Idle)]
+		Hungry { since: f32 },
+		#[transitions(Idle)]
+		#[on_enter]
+		Fleeing,
+	}
+
+	impl<W: World> crate::OnEnter for Villager<'_, W, Fleeing> {
+		fn on_enter(&mut self, _from: &'static str) { self.hunger = 0.0; }
+	}
+
+	trait Speed {
+		fn speed(&self) -> f32;
+	}
+
+	#[delegate]
+	impl<'w, W: World> Speed for VillagerEnum<'w, W> {
+		fn speed(&self) -> f32;
+	}
+
+	impl<W: World> Speed for Villager<'_, W, Idle> {
+		fn speed(&self) -> f32 { 1.0 }
+	}
+
+	impl<W: World> Speed for Villager<'_, W, Hungry> {
+		fn speed(&self) -> f32 { 1.0 - self.hunger }
+	}
+
+	impl<W: World> Speed for Villager<'_, W, Fleeing> {
+		fn speed(&self) -> f32 { 1.0 + self.world.danger() }
+	}
+
+	trait Noise {
+		fn noise(&self) -> &'static str;
+	}
+
+	#[delegate(via(state))]
+	impl<'w, W: World> Noise for VillagerEnum<'w, W> {
+		fn noise(&self) -> &'static str;
+	}
+
+	impl Noise for Idle {
+		fn noise(&self) -> &'static str { "hum" }
+	}
+
+	impl Noise for Hungry {
+		fn noise(&self) -> &'static str { "growl" }
+	}
+
+	impl Noise for Fleeing {
+		fn noise(&self) -> &'static str { "scream" }
+	}
+
+	fn idle(world: &Village) -> Villager<'_, Village, Idle> { Villager { world, hunger: 0.5, state: Idle } }
+
+	#[test]
+	fn transitions() {
+		let hungry: Transition<_, VillagerEnum<_>> = idle(&Village).transition_to(Hungry { since: 2.0 });
+
+		let Transition::ChangedTo(villager) = hungry
+		else { panic!("expected `Hungry`") };
+
+		assert_eq!(villager.hunger, 0.5);
+		assert_eq!(villager.speed(), 0.5);
+		assert_eq!(villager.noise(), "growl");
+		assert_eq!(villager.kind(), VillagerKind::Hungry);
+
+		let VillagerEnum::Hungry(hungry) = villager
+		else { panic!("expected `Hungry`") };
+
+		assert_eq!(hungry.state.since, 2.0);
+	}
+
+	#[test]
+	fn hooks() {
+		let Transition::ChangedTo(VillagerEnum::Fleeing(fleeing)) = idle(&Village).transition_to(Fleeing)
+		else { panic!("expected `Fleeing`") };
+
+		assert_eq!(fleeing.hunger, 0.0);
+		assert_eq!(fleeing.speed(), 1.9);
+	}
+
+	#[test]
+	fn tracked() {
+		let mut tracked = VillagerTracked::new(Villager { world: &Village, hunger: 0.0, state: Fleeing }.into(), 4);
+		tracked.replace_with(1, |villager| match villager {
+			VillagerEnum::Fleeing(fleeing) => fleeing.transition_to(Idle).map_unchanged(Into::into),
+			other => Transition::Unchanged(other),
+		});
+
+		assert_eq!(tracked.history().last().map(|record| (record.from, record.to)), Some(("Fleeing", "Idle")));
+		assert_eq!(tracked.get().kind(), VillagerKind::Idle);
+	}
+
+	#[test]
+	fn graph() {
+		assert_eq!(
+			VillagerEnum::<Village>::graph_mermaid(),
+			"stateDiagram-v2\n    \
+			    Idle\n    \
+			    Hungry\n    \
+			    Fleeing\n    \
+			    Idle --> Hungry\n    \
+			    Idle --> Fleeing\n    \
+			    Hungry --> Idle\n    \
+			    Fleeing --> Idle\n"
+		);
+	}
+}
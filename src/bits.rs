@@ -0,0 +1,43 @@
+/// Selects the unsigned integer with `BYTES` bytes, see [uint_bytes].
+#[doc(hidden)]
+pub struct Uint<const BYTES: usize>;
+
+#[doc(hidden)]
+pub trait UintBits {
+	type Int;
+}
+
+impl UintBits for Uint<1> {
+	type Int = u8;
+}
+
+impl UintBits for Uint<2> {
+	type Int = u16;
+}
+
+impl UintBits for Uint<4> {
+	type Int = u32;
+}
+
+impl UintBits for Uint<8> {
+	type Int = u64;
+}
+
+impl UintBits for Uint<16> {
+	type Int = u128;
+}
+
+/// Bytes of the smallest unsigned integer with one bit per variant,
+/// `<Uint<{ uint_bytes(LENGTH) }> as UintBits>::Int` is that integer.
+///
+/// Sets of more than 128 variants are rejected by the macros, so the last arm is never reached by them.
+#[doc(hidden)]
+pub const fn uint_bytes(variants: usize) -> usize {
+	match variants {
+		0..=8 => 1,
+		9..=16 => 2,
+		17..=32 => 4,
+		33..=64 => 8,
+		_ => 16,
+	}
+}
@@ -0,0 +1,29 @@
+/// Counts the identifiers in the input, expands to a constant expression of type `usize`.
+///
+/// Halves the input on every step, so the recursion depth is logarithmic on the number of identifiers.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! count_idents {
+	() => { 0usize };
+	
+	( $odd: ident $( $a: ident $b: ident )* ) => {
+		($crate::count_idents!( $( $a )* ) << 1) | 1
+	};
+	
+	( $( $a: ident $even: ident )* ) => {
+		$crate::count_idents!( $( $a )* ) << 1
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn test() {
+		assert_eq!(count_idents!(), 0);
+		assert_eq!(count_idents!(A), 1);
+		assert_eq!(count_idents!(A B), 2);
+		assert_eq!(count_idents!(A B C), 3);
+		assert_eq!(count_idents!(A B C D E F G), 7);
+		assert_eq!(count_idents!(A B C D E F G H), 8);
+	}
+}
@@ -0,0 +1,247 @@
+/// Defines a trait and registers its methods, so that it can be delegated by name.
+///
+/// Besides the trait, generates a macro with the same name as the trait, which holds its methods.
+/// `enum_delegate_impls!` (and the macros that forward to it) use that macro when a trait is delegated
+/// without listing its items: `impl trait Tick;`.
+///
+/// The generated macro is visible in the whole crate (`pub(crate)`), importing the trait also imports the macro.
+///
+/// # Input
+///
+/// ```pseudo
+/// [trait_vis] trait [trait_ident] <[generics]> : [super_traits] where [where_clause] {
+///     [ fn [method_name]<[generics]>([self_type], [args]) -> [return_type] ]
+/// }
+/// ```
+///
+/// - `[generics]`: Optional generics of the trait, must be placed inside brackets. (e.g., `<[T: Clone]>`)
+/// - `[super_traits]`: Optional super traits, must be placed inside brackets. (e.g., `: [Debug + Clone]`)
+/// - `[where_clause]`: Optional where clause of the trait, must be placed inside brackets.
+/// - Methods: same syntax as the methods in `DELEGATES`, see [enum_delegate_impls](crate::enum_delegate_impls).
+///
+/// Only methods can be registered, associated types and constants depend on the implementor.
+///
+/// # Delegating a generic trait
+///
+/// The registered methods refer to the trait's generic parameters by name,
+/// the delegation must declare parameters with the same names: `impl<[T]> trait Get<T>;`.
+///
+/// # Example
+///
+/// ```rust
+/// mod traits {
+///     declarative_type_state::delegatable_trait! {
+///         pub trait Tick {
+///             [fn tick(&mut self, delta_time: f64)]
+///             [fn elapsed(&self) -> f64]
+///         }
+///     }
+/// }
+///
+/// use declarative_type_state::delegated_enum;
+/// use traits::Tick;
+///
+/// struct Timer(f64);
+///
+/// impl Tick for Timer {
+///     fn tick(&mut self, delta_time: f64) { self.0 += delta_time; }
+///     fn elapsed(&self) -> f64 { self.0 }
+/// }
+///
+/// struct Frozen;
+///
+/// impl Tick for Frozen {
+///     fn tick(&mut self, _delta_time: f64) {}
+///     fn elapsed(&self) -> f64 { 0.0 }
+/// }
+///
+/// delegated_enum! {
+///     ENUM_OUT: {
+///         enum Clock {
+///             Timer(Timer),
+///             Frozen(Frozen),
+///         }
+///     }
+///
+///     DELEGATES: {
+///         impl trait Tick;
+///     }
+/// }
+///
+/// let mut clock = Clock::Timer(Timer(0.0));
+/// clock.tick(1.5);
+/// assert_eq!(clock.elapsed(), 1.5);
+/// ```
+#[macro_export]
+macro_rules! delegatable_trait {
+	(
+		$( #[$trait_meta: meta] )*
+		$trait_vis: vis trait $trait_ident: ident
+		$( <[ $( $trait_gen: tt )* ]> )?
+		$( : [ $( $super_trait: tt )* ] )?
+		$( where [ $( $trait_bound: tt )* ] )?
+		{
+			$( [ $( $item: tt )* ] )*
+		}
+	) => {
+		$( #[$trait_meta] )*
+		$trait_vis trait $trait_ident
+		$( < $( $trait_gen )* > )?
+		$( : $( $super_trait )* )?
+		$( where $( $trait_bound )* )?
+		{
+			$(
+				$crate::delegatable_trait! {
+					@ITEM
+					$( $item )*
+				}
+			)*
+		}
+
+		$crate::delegatable_trait! {
+			@REGISTRY ($)
+			$trait_ident {
+				$( [ $( $item )* ] )*
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@ITEM
+		async fn $( $rest: tt )*
+	) => {
+		$crate::delegatable_trait! {
+			@ITEM
+			[async] fn $( $rest )*
+		}
+	};
+
+	(@ITEM
+		$( [$( $fn_type: ident )*] )?
+		fn $fn_ident: ident
+		$( <[ $( $gens: tt )* ]> )?
+		( $( $args: tt )* )
+		$( -> $ret_ty: ty )?
+		$( where [ $( $where_clause: tt )* ] )?
+		$(;)?
+	) => {
+		$( $( $fn_type )* )?
+		fn $fn_ident
+		$( < $( $gens )* > )?
+		( $( $args )* )
+		$( -> $ret_ty )?
+		$( where $( $where_clause )* )?;
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Registry, expands into `enum_delegate_impls!` with the items of the trait
+	(@REGISTRY ($d: tt)
+		$trait_ident: ident {
+			$( $item: tt )*
+		}
+	) => {
+		$crate::paste! {
+			#[doc(hidden)]
+			#[allow(unused_macros)]
+			macro_rules! [<__delegatable_ $trait_ident>] {
+				( $d( $d invocation: tt )* ) => {
+					$crate::enum_delegate_impls! {
+						$d( $d invocation )*
+						{
+							$( $item )*
+						}
+					}
+				};
+			}
+
+			#[allow(unused_imports)]
+			pub(crate) use [<__delegatable_ $trait_ident>] as $trait_ident;
+		}
+	};
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use std::fmt::Debug;
+
+	mod traits {
+		crate::delegatable_trait! {
+			pub trait Tick {
+				[fn tick(&mut self, delta_time: f64)]
+				[fn name(&self) -> &'static str]
+			}
+		}
+
+		crate::delegatable_trait! {
+			pub trait Get<[T]>: [std::fmt::Debug] where [T: Copy] {
+				[fn get(&self) -> T]
+				[fn get_or<[U: Into<T>]>(&self, other: Option<U>) -> T]
+			}
+		}
+	}
+
+	use traits::{Get, Tick};
+
+	#[derive(Debug)]
+	pub struct State<T>(T);
+
+	impl Tick for State<i32> {
+		fn tick(&mut self, delta_time: f64) { self.0 += delta_time as i32; }
+
+		fn name(&self) -> &'static str { "i32" }
+	}
+
+	impl Tick for State<u32> {
+		fn tick(&mut self, delta_time: f64) { self.0 += delta_time as u32; }
+
+		fn name(&self) -> &'static str { "u32" }
+	}
+
+	impl<T: Copy + Debug> Get<T> for State<i32>
+	where i32: Into<T>
+	{
+		fn get(&self) -> T { self.0.into() }
+
+		fn get_or<U: Into<T>>(&self, other: Option<U>) -> T { other.map(Into::into).unwrap_or_else(|| self.get()) }
+	}
+
+	impl<T: Copy + Debug> Get<T> for State<u32>
+	where u32: Into<T>
+	{
+		fn get(&self) -> T { self.0.into() }
+
+		fn get_or<U: Into<T>>(&self, other: Option<U>) -> T { other.map(Into::into).unwrap_or_else(|| self.get()) }
+	}
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			#[derive(Debug)]
+			enum StateEnum {
+				Int(State<i32>),
+				UInt(State<u32>),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Tick;
+
+			impl<[T: Copy + Debug]> trait traits::Get<T>
+			where [i32: Into<T>, u32: Into<T>];
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut state = StateEnum::Int(State(5));
+		state.tick(2.0);
+		assert_eq!(state.name(), "i32");
+		assert_eq!(Get::<i64>::get(&state), 7);
+		assert_eq!(Get::<i64>::get_or(&state, Some(3_i8)), 3);
+
+		let mut state = StateEnum::UInt(State(1));
+		state.tick(1.0);
+		assert_eq!(state.name(), "u32");
+		assert_eq!(Get::<i64>::get(&state), 2);
+	}
+}
@@ -5,11 +5,14 @@
 /// - From<Variant> for Enum
 /// - TryFrom<Enum> for Variant
 ///
+///
 /// # Input
 /// - `ENUM_OUT`: Defines the output enum with its metadata, visibility, 
 ///   name, generics, and variants.
 /// - `DELEGATES`: Specifies the traits and methods to be implemented for the enum. 
 ///   All variants of the enum must implement the traits/methods.
+/// - `KIND`: Optional, generates a fieldless kind of the enum, see [enum_kind!](crate::enum_kind).
+/// - `VISITOR`: Optional, generates a visitor trait for the enum, see [enum_visitor!](crate::enum_visitor).
 ///
 /// ## ENUM_OUT:
 /// 
@@ -118,22 +121,11 @@ macro_rules! delegated_enum {
 		    }
 	    }
 	    
-	    DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-		    
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+	    DELEGATES: $delegates: tt
+	    
+	    $( KIND: $kind: tt )?
+	    
+	    $( VISITOR: $visitor: tt )?
     ) => {
 	    $( #[$enum_meta] )*
 	    $enum_vis enum $enum_ident
@@ -152,6 +144,26 @@ macro_rules! delegated_enum {
 		    }
 	    }
 	    
+	    $crate::enum_kind! {
+		    @SECTION [ $( $kind )? ]
+		    $enum_vis enum $enum_ident
+		    $( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+		    {
+			    $( $var_ident ( $var_ty ) ),*
+		    }
+	    }
+	    
+	    $crate::enum_visitor! {
+		    @SECTION [ $( $visitor )? ]
+		    $enum_vis enum $enum_ident
+		    $( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+		    {
+			    $( $var_ident ( $var_ty ) ),*
+		    }
+	    }
+	    
 	    $crate::enum_delegate_impls! {
 		    ENUM_IN: {
 			    $enum_ident
@@ -162,22 +174,7 @@ macro_rules! delegated_enum {
 		        }
 		    }
 		    
-		    DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )? 
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-			    
-			    $(
-				    impl {
-					    $([ $( $std_impl )* ])*
-				    }
-			    )?   
-		    }
+		    DELEGATES: $delegates
 	    }
     };
 }
@@ -285,6 +282,7 @@ mod test_generics {
 		}
 	}
 
+	#[allow(clippy::needless_lifetimes)]
 	impl <'a, S> Tick for PhantomData<&'a S> {
 		fn tick(&mut self, delta_time: f64) {
 			todo!()
@@ -335,6 +333,7 @@ mod test_generics_on_trait {
 		}
 	}
 
+	#[allow(clippy::needless_lifetimes)]
 	impl<'a, S, G> Tick<G> for PhantomData<&'a S> {
 		fn tick(&mut self, delta_time: G) {
 			todo!()
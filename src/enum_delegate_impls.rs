@@ -51,6 +51,14 @@
 ///     - `: [const_type]`: Type of the associated constant.
 ///     - `= [expr]`: Value of the associated constant.
 ///
+/// ## DELEGATES - Registered traits:
+/// Traits defined with [delegatable_trait](crate::delegatable_trait) can be delegated by name,
+/// their methods are provided by the macro generated alongside the trait, which must be in scope.
+///
+/// ```pseudo
+/// impl<[generics]> trait [trait_type] [where_clause];
+/// ```
+///
 /// ## DELEGATES - Methods:
 /// Additional method implementations.
 /// All variants of the enum must have methods with the same name and number of arguments.
@@ -65,7 +73,90 @@
 /// - `<[generics]>`: Optional generics for the method, must be placed inside brackets.
 /// - `([self_type], [args])`: Method parameters including self-reference.
 /// - `-> [return_type]`: Return type of the method (optional if return type is `()`).
-/// 
+///
+/// ## DELEGATES - async and `impl Trait`:
+/// `async fn` methods await the call of each variant, the variants must implement the method as `async fn`.
+/// `[async] fn` generates an `async fn` which calls the variants without awaiting, the variants implement it as a regular `fn`.
+///
+/// Methods returning `-> impl Iterator<..>` or `-> impl Future<..>` return a local enum
+/// with one variant per variant's return type, which delegates `Iterator::next`/`Future::poll`,
+/// along with `DoubleEndedIterator` and `ExactSizeIterator` when all the variants' iterators implement them.
+/// The bounds may also list lifetimes and the markers `Send`, `Sync` and `Unpin`, in any order:
+/// `-> impl Send + Iterator<Item = u32> + '_`.
+/// Other `impl Trait` return types are not supported, since each variant may return a different type.
+///
+/// ## DELEGATES - Typed receivers:
+/// Each variant is called through the same receiver as the enum, the wrapper must be named without a path:
+/// - `self: Box<Self>`: the enum is moved out of the box, then the variant is boxed again.
+/// - `self: Pin<&mut Self>`: the variant is pinned in place. To keep it pinned, the enum gets an `Unpin` impl
+///   that only holds if every variant is `Unpin`, and must not implement `Drop`:
+///   a manual `impl Unpin` or `impl Drop` for the enum is a compile error (conflicting implementations).
+/// - `self: Rc<Self>` / `self: Arc<Self>`: the enum is unwrapped with `unwrap_or_clone`, then the variant is wrapped again.
+///   If the pointer is shared, the whole enum is cloned (the enum must implement `Clone`)
+///   and the variant is called through a new pointer: the sharing is dropped, the other owners keep the old enum.
+///
+/// ## DELEGATES - Wrapped returns:
+/// Methods that return `Self` in each variant's type are declared with the return type inside brackets,
+/// the value returned by each variant is converted into the enum with `From`:
+/// - `-> [Self]`
+/// - `-> [Transition<Self, Next>]`: `Unchanged(variant)` becomes `Unchanged(enum)`, `ChangedTo` is kept as is.
+///
+/// The receiver must be `self`, `&self` or `&mut self`.
+///
+/// ## DELEGATES - Associated functions and constants:
+/// Functions without a `self` parameter are delegated to the type of a chosen variant,
+/// a function returning `Self` constructs that variant:
+/// `[[@default Idle] fn default() -> Self]` generates `Self::Idle(<Idle's type>::default())`.
+///
+/// Associated constants can aggregate the constant of each variant's type,
+/// with `[@max]`, `[@min]` or `[@sum]`: `[[@max] const SIZE: usize]`.
+///
+/// The items are called on the variant's type, so the trait must be in scope.
+///
+/// ## DELEGATES - Opted-out variants:
+/// Variants that don't implement a trait are listed before the delegation,
+/// the methods end with a fallback body, which runs for those variants:
+///
+/// ```pseudo
+/// [@skip [var_name_A], [var_name_B]] impl trait [trait_type] {
+///     [ fn [method_name]([self_type], [args]) -> [return_type] else { [fallback] } ]
+/// }
+/// ```
+///
+/// Methods without fallback are delegated to every variant.
+///
+/// ## DELEGATES - Projection:
+/// A trait implemented by a field of each variant's type is delegated to that field with `via`,
+/// the field may also be nested or a tuple index:
+///
+/// ```pseudo
+/// impl<[generics]> trait [trait_type] via .[field].[nested_field] [where_clause] { [items] }
+/// ```
+///
+/// Only methods are projected, associated functions and constants still use each variant's type.
+/// Typed receivers (`self: Box<Self>`, ..) cannot be projected.
+///
+/// ## DELEGATES - Operators:
+/// Operator traits of `std::ops`, `std::cmp` and `std::hash` are implemented by name,
+/// without listing their items:
+///
+/// ```pseudo
+/// impl<[generics]> operator [operator] [where_clause] [else [policy]];
+/// impl<[generics]> operator Index<[index_type]> -> [output_type] [where_clause];
+/// ```
+///
+/// - `Add`, `Sub`, `Mul`, `Div`, `Rem`, `BitAnd`, `BitOr`, `BitXor`: both operands must be the same variant,
+///   whose type implements the operator with `Output` being that type.
+///   Different variants panic (`else panic`, the default), or with `else None` the output is `Option<Self>`.
+/// - `Neg`, `Not`: applied to the variant.
+/// - `Index`, `IndexMut`: every variant's type indexes into the same output type.
+/// - `PartialEq`, `Eq`: different variants are never equal.
+/// - `PartialOrd`: different variants compare by declaration order of the variants (`else discriminant`, the default),
+///   or to `None` (`else None`), or panic (`else panic`).
+///   When `Ord` is delegated too, `PartialOrd` takes no policy and is implemented as `Some(self.cmp(other))`.
+/// - `Ord`: different variants compare by declaration order (`else discriminant`, the default), or panic (`else panic`).
+/// - `Hash`: hashes the discriminant, then the variant.
+///
 /// # Example
 /// 
 /// ```rust
@@ -103,6 +194,35 @@
 /// assert_eq!(format!("{debug:?}"), format!("{:?}", 5_i32));
 /// assert_eq!(debug.to_string(), 5.to_string());
 /// ```
+///
+/// An enum that delegates `self: Pin<&mut Self>` cannot implement `Drop`, which could move a pinned variant out:
+///
+/// ```compile_fail
+/// # use declarative_type_state::enum_delegate_impls;
+/// # use std::pin::Pin;
+/// trait Step {
+///     fn step(self: Pin<&mut Self>);
+/// }
+///
+/// enum Job<A, B> {
+///     A(A),
+///     B(B),
+/// }
+///
+/// enum_delegate_impls! {
+///     ENUM_IN: { Job<[A, B]> where [A: Step, B: Step] { A(A), B(B) } }
+///
+///     DELEGATES: {
+///         impl<[A: Step, B: Step]> trait Step {
+///             [fn step(self: Pin<&mut Self>)]
+///         }
+///     }
+/// }
+///
+/// impl<A, B> Drop for Job<A, B> {
+///     fn drop(&mut self) {}
+/// }
+/// ```
 #[macro_export]
 macro_rules! enum_delegate_impls {
     (
@@ -111,156 +231,1485 @@ macro_rules! enum_delegate_impls {
 		    $( <[ $( $enum_gen: tt )* ]> )?
 			$( where [ $( $enum_bound: tt )* ] )?
 		    {
-				$( $var_ident: ident ( $var_ty: ty ) ),* // var_ty is ignored
+				$( $var_ident: ident ( $var_ty: ty ) ),*
 			    $(,)?
 	        }
 	    }
 	    
 	    DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-		    
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
+		    $( $delegates: tt )*
 	    }
     ) => {
 	    $crate::enum_delegate_impls! {
-		    @TOKENIZE
-		    { $( $( $enum_gen )* )? }
-			{ $( $( $enum_bound )* )? }
-		    { $( $( $enum_gen )* )? }
-			{ $( $( $enum_bound )* )? }
-		    $enum_ident {
-		        $( $var_ident ),*
-		    }
+		    @DELEGATES
+		    [
+			    { $( $( $enum_gen )* )? }
+			    { $( $( $enum_bound )* )? }
+			    $enum_ident {
+			        []
+			        $( $var_ident ($var_ty) ),*
+			    }
+		    ]
 		    
-		    DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )? 
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-			    
-			    $(
-				    impl { 
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+		    $( $delegates )*
+	    }
+	    
+	    $crate::enum_delegate_impls! {
+		    @SCAN
+		    [
+			    { $( $( $enum_gen )* )? }
+			    { $( $( $enum_bound )* )? }
+			    $enum_ident {
+			        []
+			        $( $var_ident ($var_ty) ),*
+			    }
+		    ]
+		    []
+		    []
+		    []
+		    $( $delegates )*
 	    }
     };
 	
 	//------------------------------------------------------------------------------------------------------------------
-	(@TOKENIZE
-		$generics_tt: tt
-		$bounds_tt: tt
-		{ $( $generic: tt )* }
-		{ $( $bound: tt )* }
-		$enum_ident: ident 
-		$enum_vars: tt
+	// `PartialOrd` depends on whether `Ord` is delegated too, so it is implemented by `@SCAN` instead
+	(@DELEGATES
+		$enum_tt: tt
 	
-		DELEGATES: {
+		impl $( <[ $( $op_gen: tt )* ]> )?
+		operator PartialOrd
+		$( where [ $( $op_bound: tt )* ] )?
+		$( else $policy: ident )?
+		;
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			$enum_tt
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Trait, the path is munched until the start of the body
+	(@DELEGATES
+		$enum_tt: tt
+	
+		impl $( <[ $( $trait_gen: tt )* ]> )?
+		trait
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_PATH
+			$enum_tt
+			[ $( <[ $( $trait_gen )* ]> )? ]
+			[]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Trait with opted-out variants, the list is carried at the start of the path
+	(@DELEGATES
+		$enum_tt: tt
+	
+		[@skip $( $skip_var: ident ),* $(,)?]
+		impl $( <[ $( $trait_gen: tt )* ]> )?
+		trait
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_PATH
+			$enum_tt
+			[ $( <[ $( $trait_gen )* ]> )? ]
+			[ @skip [ $( $skip_var ),* ] ]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Operator, munched until the `;` that ends it
+	(@DELEGATES
+		$enum_tt: tt
+	
+		impl $( <[ $( $op_gen: tt )* ]> )?
+		operator
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@OPERATOR
+			$enum_tt
+			[ $( <[ $( $op_gen )* ]> )? ]
+			[]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Inherent methods
+	(@DELEGATES
+		[
+			{ $( $generic: tt )* }
+			{ $( $bound: tt )* }
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+	
+		impl {
+			$( $std_impl: tt )*
+		}
+		
+		$( $rest: tt )*
+	) => {
+		impl<$( $generic )*> 
+		$enum_ident<$( $generic )*> 
+		where $( $bound )*
+		{
 			$(
-				impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-				$( where [ $( $trait_bound: tt )* ] )?
-				{
-			        $( $item: tt )*
-		        }
+				$crate::enum_delegate_impls! {
+					@ITEM
+					$enum_ident
+					$enum_vars
+					$std_impl
+				}
 			)*
+		}
 		
-			$(
-				impl { 
-					$( $std_impl: tt )*
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			[
+				{ $( $generic )* }
+				{ $( $bound )* }
+				$enum_ident
+				$enum_vars
+			]
+			
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	(@DELEGATES $enum_tt: tt) => {};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Collects the `PartialOrd` delegations, whether `Ord` is delegated, and whether a method takes `self: Pin<&mut Self>`,
+	// the bodies of the delegations are spliced in so that their items are scanned too
+	(@SCAN
+		$enum_tt: tt
+		[ $( $partial_ord: tt )* ]
+		$ord_tt: tt
+		$pin_tt: tt
+		
+		impl $( <[ $( $op_gen: tt )* ]> )?
+		operator PartialOrd
+		$( where [ $( $op_bound: tt )* ] )?
+		$( else $policy: ident )?
+		;
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@SCAN
+			$enum_tt
+			[
+				$( $partial_ord )*
+				{
+					{ $( $( $op_gen )* )? }
+					{ $( $( $op_bound )* )? }
+					[ $( $policy )? ]
 				}
-			)?
+			]
+			$ord_tt
+			$pin_tt
+			$( $rest )*
+		}
+	};
+	
+	(@SCAN
+		$enum_tt: tt
+		$partial_ord_tt: tt
+		$ord_tt: tt
+		$pin_tt: tt
+		
+		impl $( <[ $( $op_gen: tt )* ]> )?
+		operator Ord
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@SCAN
+			$enum_tt
+			$partial_ord_tt
+			[Ord]
+			$pin_tt
+			$( $rest )*
+		}
+	};
+	
+	(@SCAN
+		$enum_tt: tt
+		$partial_ord_tt: tt
+		$ord_tt: tt
+		$pin_tt: tt
+		
+		[
+			$fn_vis: vis
+			$( [$( $fn_type: ident )*] )?
+			fn $fn_ident: ident
+			$( < [ $( $gens: tt )* ] > )?
+			( self: Pin<&mut Self> $( $params: tt )* )
+			$( $sig: tt )*
+		]
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@SCAN
+			$enum_tt
+			$partial_ord_tt
+			$ord_tt
+			[Pin]
+			$( $rest )*
+		}
+	};
+	
+	(@SCAN
+		$enum_tt: tt
+		$partial_ord_tt: tt
+		$ord_tt: tt
+		$pin_tt: tt
+		
+		{ $( $body: tt )* }
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@SCAN
+			$enum_tt
+			$partial_ord_tt
+			$ord_tt
+			$pin_tt
+			$( $body )*
+			$( $rest )*
+		}
+	};
+	
+	(@SCAN
+		$enum_tt: tt
+		$partial_ord_tt: tt
+		$ord_tt: tt
+		$pin_tt: tt
+		
+		$next: tt
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@SCAN
+			$enum_tt
+			$partial_ord_tt
+			$ord_tt
+			$pin_tt
+			$( $rest )*
 		}
+	};
+	
+	(@SCAN
+		$enum_tt: tt
+		[ $( $partial_ord: tt )* ]
+		$ord_tt: tt
+		$pin_tt: tt
 	) => {
 		$(
-			$crate::enum_delegate_impls! {
-				@TRAIT_IMPL
+			$crate::enum_delegate_ops! {
+				@PARTIAL_ORD_WITH
+				$enum_tt
+				$ord_tt
+				$partial_ord
+			}
+		)*
+		
+		$crate::enum_delegate_impls! {
+			@PIN_GUARD
+			$pin_tt
+			$enum_tt
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Pinned variants are projected from the pinned enum, which is only sound if the enum cannot unpin them:
+	// it must not implement `Unpin` unless all the variants do, nor `Drop`, which could move a variant out.
+	// Manual impls of either conflict with the ones below, same as `pin-project`.
+	(@PIN_GUARD [] $enum_tt: tt) => {};
+	
+	(@PIN_GUARD
+		[Pin]
+		[
+			{ $( $enum_gen: tt )* }
+			{ $( $enum_bound: tt )* }
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident ( $var_ty: ty ) ),* }
+		]
+	) => {
+		impl<'__pin, $( $enum_gen )*> ::core::marker::Unpin for $enum_ident<$( $enum_gen )*>
+		where
+			$( $crate::PinGuard<'__pin, $var_ty>: ::core::marker::Unpin, )*
+			$( $enum_bound )*
+		{}
+		
+		const _: () = {
+			#[allow(dead_code)]
+			trait MustNotImplDrop {}
+			
+			#[allow(drop_bounds)]
+			impl<__T: ::core::ops::Drop> MustNotImplDrop for __T {}
+			
+			impl<$( $enum_gen )*> MustNotImplDrop for $enum_ident<$( $enum_gen )*> where $( $enum_bound )* {}
+		};
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	(@OPERATOR
+		$enum_tt: tt
+		$op_gen_tt: tt
+		[ $( $op: tt )* ]
+		
+		;
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_ops! {
+			$enum_tt
+			$op_gen_tt
+			$( $op )*
+		}
+		
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			$enum_tt
+			$( $rest )*
+		}
+	};
+	
+	(@OPERATOR
+		$enum_tt: tt
+		$op_gen_tt: tt
+		[ $( $op: tt )* ]
+		
+		$next: tt
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@OPERATOR
+			$enum_tt
+			$op_gen_tt
+			[ $( $op )* $next ]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Trait delegated to a field of each variant, the body is matched by its first token to end the projection
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
+		
+		via $( . $field: tt )+
+		where [ $( $trait_bound: tt )* ]
+		$body: tt
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_VIA
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[ $( . $field )+ ]
+			[ where [ $( $trait_bound )* ] ]
+			$body
+			$( $rest )*
+		}
+	};
+	
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
+		
+		via $( . $field: tt )+
+		{ $( $item: tt )* }
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_VIA
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[ $( . $field )+ ]
+			[]
+			{ $( $item )* }
+			$( $rest )*
+		}
+	};
+	
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
+		
+		via $( . $field: tt )+
+		;
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_VIA
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[ $( . $field )+ ]
+			[]
+			;
+			$( $rest )*
+		}
+	};
+	
+	// The projection replaces the one in the variants for this trait only
+	(@TRAIT_VIA
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var: tt )* }
+		]
+		$trait_gen_tt: tt
+		$path_tt: tt
+		$proj_tt: tt
+		$where_tt: tt
+		$body: tt
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT
+			[
 				$generics_tt
 				$bounds_tt
+				$enum_ident
+				{ $proj_tt $( $var )* }
+			]
+			$trait_gen_tt
+			$path_tt
+			$where_tt
+			$body
+		}
+		
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			[
 				$generics_tt
 				$bounds_tt
 				$enum_ident
-				$enum_vars
-				
-				impl $( <[ $( $trait_gen )*  ]> )?
-				trait $trait_ty
-				$( where [ $( $trait_bound )* ] )?
-				{
-			        $( $item )*
-		        }
-			}
-		)*
+				{ $via $( $var )* }
+			]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
 		
-		impl<$( $generic )*> 
-		$enum_ident<$( $generic )*> 
-		where $( $bound )*
-		{
-			$(
-				$(
-					$crate::enum_delegate_impls! {
-						@ITEM
-						$enum_ident
-						$enum_vars
-						$std_impl
-					}
-				)*
-			)?
+		where [ $( $trait_bound: tt )* ]
+		$body: tt
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[ where [ $( $trait_bound )* ] ]
+			$body
+		}
+		
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			$enum_tt
+			$( $rest )*
 		}
 	};
 	
 	//------------------------------------------------------------------------------------------------------------------
-	(@TRAIT_IMPL
-		$generics_tt: tt
-		$bounds_tt: tt
-		{ $( $enum_gen: tt )* }
-		{ $( $enum_bound: tt )* }
-		$enum_ident: ident
-		$enum_vars: tt
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
+		
+		{ $( $item: tt )* }
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[]
+			{ $( $item )* }
+		}
+		
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			$enum_tt
+			$( $rest )*
+		}
+	};
 	
-		impl $( <[ $( $trait_gen: tt )* ]> )? 
-		trait $trait_ty: path
-		$( where [ $( $trait_bound: tt )* ] )?
+	//------------------------------------------------------------------------------------------------------------------
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		$path_tt: tt
+		
+		;
+		
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT
+			$enum_tt
+			$trait_gen_tt
+			$path_tt
+			[]
+			;
+		}
+		
+		$crate::enum_delegate_impls! {
+			@DELEGATES
+			$enum_tt
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	(@TRAIT_PATH
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		[ $( $path: tt )* ]
+		
+		$next: tt
+		$( $rest: tt )*
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_PATH
+			$enum_tt
+			$trait_gen_tt
+			[ $( $path )* $next ]
+			$( $rest )*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Items listed in the delegation, each item carries the opted-out variants
+	(@TRAIT
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+		[ $( $trait_gen: tt )* ]
+		[ @skip $skip_tt: tt $( $path: tt )* ]
+		[ $( $where_clause: tt )* ]
+		
+		{ $( [ $( $item: tt )* ] )* }
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_IMPL
+			$generics_tt
+			$bounds_tt
+			$generics_tt
+			$bounds_tt
+			$enum_ident
+			$enum_vars
+			impl $( $trait_gen )*
+			trait $( $path )*
+			$( $where_clause )*
+			{
+				$( [ @skip $skip_tt $( $item )* ] )*
+			}
+		}
+	};
+	
+	(@TRAIT
+		$enum_tt: tt
+		$trait_gen_tt: tt
+		[ @skip $skip_tt: tt $( $path: tt )* ]
+		$where_tt: tt
+		
+		;
+	) => {
+		compile_error!(concat!(
+			"Cannot opt variants out of `", stringify!($( $path )*), "`, its items are not listed.\n\
+			 Help: list the items of the trait, with an `else` fallback for the opted-out variants."
+		));
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Items listed in the delegation
+	(@TRAIT
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+		[ $( $trait_gen: tt )* ]
+		[ $( $path: tt )* ]
+		[ $( $where_clause: tt )* ]
+		
+		{ $( $item: tt )* }
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_IMPL
+			$generics_tt
+			$bounds_tt
+			$generics_tt
+			$bounds_tt
+			$enum_ident
+			$enum_vars
+			impl $( $trait_gen )*
+			trait $( $path )*
+			$( $where_clause )*
+			{
+				$( $item )*
+			}
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Items registered with `delegatable_trait!`, the trait's macro appends them to the invocation
+	(@TRAIT
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+		[ $( $trait_gen: tt )* ]
+		[ $( $path: tt )* ]
+		[ $( $where_clause: tt )* ]
+		
+		;
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_MACRO
+			{
+				@TRAIT_IMPL
+				$generics_tt
+				$bounds_tt
+				$generics_tt
+				$bounds_tt
+				$enum_ident
+				$enum_vars
+				impl $( $trait_gen )*
+				trait $( $path )*
+				$( $where_clause )*
+			}
+			[]
+			[ $( $path )* ]
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// The macro path is the trait path without generic arguments
+	(@TRAIT_MACRO
+		$args: tt
+		[ $( $macro_path: tt )* ]
+		[ < $( $trait_arg: tt )* ]
+	) => {
+		$( $macro_path )* ! $args
+	};
+	
+	(@TRAIT_MACRO
+		$args: tt
+		[ $( $macro_path: tt )* ]
+		[]
+	) => {
+		$( $macro_path )* ! $args
+	};
+	
+	(@TRAIT_MACRO
+		$args: tt
+		[ $( $macro_path: tt )* ]
+		[ $next: tt $( $rest: tt )* ]
+	) => {
+		$crate::enum_delegate_impls! {
+			@TRAIT_MACRO
+			$args
+			[ $( $macro_path )* $next ]
+			[ $( $rest )* ]
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	(@TRAIT_IMPL
+		$generics_tt: tt
+		$bounds_tt: tt
+		{ $( $enum_gen: tt )* }
+		{ $( $enum_bound: tt )* }
+		$enum_ident: ident
+		$enum_vars: tt
+	
+		impl $( <[ $( $trait_gen: tt )* ]> )? 
+		trait $trait_ty: path
+		$( where [ $( $trait_bound: tt )* ] )?
+		{
+	        $( $item: tt )*
+        }
+	) => {
+		impl$(< $( $trait_gen )* >)?
+		$trait_ty
+		for $enum_ident<$( $enum_gen )*>
+		$( where $( $trait_bound )* )?
+		{
+			$(
+				$crate::enum_delegate_impls! {
+					@ITEM
+					$enum_ident
+					$enum_vars
+					$item
+				}
+			)*
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Item of a trait with opted-out variants, munched until its `else` fallback
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[ @skip [ $( $skip_var: ident ),* ] $( $item: tt )* ]
+	) => {
+		$crate::enum_delegate_impls! {
+			@SKIP_ITEM
+			{ $enum_ident $enum_vars [ $( $skip_var ),* ] }
+			[]
+			$( $item )*
+		}
+	};
+	
+	(@SKIP_ITEM $ctx: tt [ $( $sig: tt )* ] else $fallback: block) => {
+		$crate::enum_delegate_impls! { @FALLBACK $ctx [ $( $sig )* ] $fallback }
+	};
+	
+	// Without fallback, every variant must implement the item
+	(@SKIP_ITEM { $enum_ident: ident $enum_vars: tt $skip_tt: tt } [ $( $sig: tt )* ]) => {
+		$crate::enum_delegate_impls! { @ITEM $enum_ident $enum_vars [ $( $sig )* ] }
+	};
+	
+	(@SKIP_ITEM $ctx: tt [ $( $sig: tt )* ] $next: tt $( $rest: tt )*) => {
+		$crate::enum_delegate_impls! { @SKIP_ITEM $ctx [ $( $sig )* $next ] $( $rest )* }
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self) else {}
+	(@FALLBACK
+		$ctx: tt
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( $self_kw: ident $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+		$fallback: block
+	) => {
+	    $crate::enum_delegate_impls! {
+		    @FALLBACK_FN ($)
+		    $ctx
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( $self_kw $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    $self_kw
+		    ($($arg_ident),*)
+		    $fallback
+	    }
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&self) else {}
+	(@FALLBACK
+		$ctx: tt
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( & $self_kw: ident $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+		$fallback: block
+	) => {
+	    $crate::enum_delegate_impls! {
+		    @FALLBACK_FN ($)
+		    $ctx
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( & $self_kw $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    $self_kw
+		    ($($arg_ident),*)
+		    $fallback
+	    }
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&mut self) else {}
+	(@FALLBACK
+		$ctx: tt
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &mut $self_kw: ident $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+		$fallback: block
+	) => {
+	    $crate::enum_delegate_impls! {
+		    @FALLBACK_FN ($)
+		    $ctx
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( &mut $self_kw $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    $self_kw
+		    ($($arg_ident),*)
+		    $fallback
+	    }
+	};
+	
+	// The opted-out variants are matched without binding, so that the fallback may use `self`.
+	// `self` is taken from the input, the fallback refers to it.
+	(@FALLBACK_FN ($d: tt)
+		{
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+			[ $( $skip_var: ident ),* ]
+		}
+		[
+			$fn_vis: vis
+			[ $( $fn_type: ident )* ]
+			$fn_ident: ident
+			[ $( $gens: tt )* ]
+			( $( $params: tt )* )
+			[ $( $ret_ty: ty )? ]
+			[ $( $where_clause: tt )* ]
+		]
+		$self: ident
+		$args: tt
+		$fallback: block
+	) => {
+	    $fn_vis
+	    $( $fn_type )*
+	    fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    $( -> $ret_ty )?
+	    where $( $where_clause )*
+		{
+			macro_rules! __binding {
+				$( ($skip_var $d var: ident) => { _ }; )*
+				($d other: ident $d var: ident) => { $d var };
+			}
+			
+			macro_rules! __call {
+				$( ($skip_var $d var: ident) => { $fallback }; )*
+				($d other: ident $d var: ident) => { $crate::enum_delegate_impls!(@VIA $via $d var).$fn_ident $args };
+			}
+			
+			match $self {
+			    $(
+			        Self::$var_ident(__binding!($var_ident var)) => { __call!($var_ident var) }
+			    )*
+		    }
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// [@default Var] fn() -> Self, the variant's type constructs the enum
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    [@default $default_var: ident]
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( $( $arg_ident: ident: $arg_ty: ty ),*  $(,)? )
+		    -> Self
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @DEFAULT_FN
+		    $default_var
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( $( $arg_ident: $arg_ty ),* )
+			    [ Self ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    (Self::$default_var)
+		    ($($arg_ident),*)
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// [@default Var] fn(), the variant's type returns the value as is
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    [@default $default_var: ident]
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( $( $arg_ident: ident: $arg_ty: ty ),*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @DEFAULT_FN
+		    $default_var
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( $( $arg_ident: $arg_ty ),* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    (std::convert::identity)
+		    ($($arg_ident),*)
+	    }
+    };
+
+	// The type of the default variant is found by a local macro, with one rule per variant
+	(@DEFAULT_FN
+		$default_var: ident
+		{ $via: tt $( $var_ident: ident ( $var_ty: ty ) ),* }
+		[
+			$fn_vis: vis
+			[ $( $fn_type: ident )* ]
+			$fn_ident: ident
+			[ $( $gens: tt )* ]
+			( $( $params: tt )* )
+			[ $( $ret_ty: tt )* ]
+			[ $( $where_clause: tt )* ]
+		]
+		$wrap: tt
+		$args: tt
+	) => {
+	    $fn_vis
+	    $( $fn_type )*
+	    fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    $( -> $ret_ty )*
+	    where $( $where_clause )*
+		{
+			macro_rules! __variant_ty {
+				$( ($var_ident) => { $var_ty }; )*
+			}
+
+			$wrap(<__variant_ty!($default_var)>::$fn_ident $args)
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// [@max] const, aggregates the constant of each variant's type
+	(@ITEM
+		$enum_ident: ident { $via: tt $( $var_ident: ident ( $var_ty: ty ) ),* }
+
+		[
+		    [@ $aggregate: ident]
+		    $const_vis: vis
+		    const $const_ident: ident: $const_ty: ty
+		    $(;)?
+		]
+	) => {
+		$const_vis const $const_ident: $const_ty = $crate::enum_delegate_impls!(@AGGREGATE $aggregate $const_ident $( $var_ty ),*);
+	};
+
+	(@AGGREGATE max $const_ident: ident $first: ty $(, $var_ty: ty )*) => {{
+		let mut value = <$first>::$const_ident;
+		$( if <$var_ty>::$const_ident > value { value = <$var_ty>::$const_ident; } )*
+		value
+	}};
+
+	(@AGGREGATE min $const_ident: ident $first: ty $(, $var_ty: ty )*) => {{
+		let mut value = <$first>::$const_ident;
+		$( if <$var_ty>::$const_ident < value { value = <$var_ty>::$const_ident; } )*
+		value
+	}};
+
+	(@AGGREGATE sum $const_ident: ident $first: ty $(, $var_ty: ty )*) => {
+		<$first>::$const_ident $( + <$var_ty>::$const_ident )*
+	};
+
+	(@AGGREGATE $aggregate: ident $const_ident: ident $( $var_ty: ty ),*) => {
+		compile_error!(concat!(
+			"Unknown aggregate `@", stringify!($aggregate), "` for the constant `", stringify!($const_ident), "`.\n\
+			 Help: the supported aggregates are `[@max]`, `[@min]` and `[@sum]`."
+		))
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// async fn(self), the call of each variant is awaited
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    async fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @AWAIT_FN
+		    $enum_vars
+		    [
+			    $fn_vis
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( self $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// async fn(&self), the call of each variant is awaited
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    async fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @AWAIT_FN
+		    $enum_vars
+		    [
+			    $fn_vis
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( &self $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// async fn(&mut self), the call of each variant is awaited
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    async fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &mut self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @AWAIT_FN
+		    $enum_vars
+		    [
+			    $fn_vis
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( &mut self $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// async fn without a self parameter, reported by `fn() <- INVALID`
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[ $fn_vis: vis async fn $( $rest: tt )* ]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @ITEM
+		    $enum_ident $enum_vars
+		    [ $fn_vis [async] fn $( $rest )* ]
+	    }
+    };
+	
+	(@AWAIT_FN
+		{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		[
+			$fn_vis: vis
+			$fn_ident: ident
+			[ $( $gens: tt )* ]
+			( $( $params: tt )* )
+			[ $( $ret_ty: ty )? ]
+			[ $( $where_clause: tt )* ]
+		]
+		$self: ident
+		$args: tt
+	) => {
+	    $fn_vis async fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    $( -> $ret_ty )?
+	    where $( $where_clause )*
+		{
+			match $self {
+			    $(
+			        Self::$var_ident(var) => { $crate::enum_delegate_impls!(@VIA $via var).$fn_ident $args.await }
+			    )*
+		    }
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self) -> impl Trait
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> impl $( $ret: tt )*
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @IMPL_RET
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( self $(, $arg_ident: $arg_ty )* )
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+		    []
+		    $( $ret )*
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&self) -> impl Trait
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> impl $( $ret: tt )*
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @IMPL_RET
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( &self $(, $arg_ident: $arg_ty )* )
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+		    []
+		    $( $ret )*
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&mut self) -> impl Trait
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+	
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &mut self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> impl $( $ret: tt )*
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @IMPL_RET
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( &mut self $(, $arg_ident: $arg_ty )* )
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+		    []
+		    $( $ret )*
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self) -> [Self]
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> [ $( $wrapped: tt )* ]
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @WRAP_RET
+		    [ $( $wrapped )* ]
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( self $(, $arg_ident: $arg_ty )* )
+				    [ $( $( $where_clause )* )? ]
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&self) -> [Self]
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> [ $( $wrapped: tt )* ]
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @WRAP_RET
+		    [ $( $wrapped )* ]
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( &self $(, $arg_ident: $arg_ty )* )
+				    [ $( $( $where_clause )* )? ]
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(&mut self) -> [Self]
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( &mut self $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    -> [ $( $wrapped: tt )* ]
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @WRAP_RET
+		    [ $( $wrapped )* ]
+		    {
+			    $enum_vars
+			    [
+				    $fn_vis
+				    [ $( $( $fn_type )* )? ]
+				    $fn_ident
+				    [ $( $( $gens )* )? ]
+				    ( &mut self $(, $arg_ident: $arg_ty )* )
+				    [ $( $( $where_clause )* )? ]
+			    ]
+			    self
+			    ($($arg_ident),*)
+		    }
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// The return type inside brackets is converted from each variant's return type
+	(@WRAP_RET [ Self ] $fn_tt: tt) => {
+		$crate::enum_delegate_impls! { @WRAP_FN [ Self ] ( std::convert::Into::<Self>::into ) $fn_tt }
+	};
+
+	(@WRAP_RET [ Transition < Self, $next: ty $(,)? > ] $fn_tt: tt) => {
+		$crate::enum_delegate_impls! {
+			@WRAP_FN
+			[ $crate::Transition<Self, $next> ]
+			( |transition| $crate::Transition::map_unchanged(transition, std::convert::Into::<Self>::into) )
+			$fn_tt
+		}
+	};
+
+	// The path before `Transition` is ignored
+	(@WRAP_RET [ $( :: )? $segment: ident :: $( $rest: tt )* ] $fn_tt: tt) => {
+		$crate::enum_delegate_impls! { @WRAP_RET [ $( $rest )* ] $fn_tt }
+	};
+
+	(@WRAP_RET [ $( $other: tt )* ] $fn_tt: tt) => {
+		compile_error!(concat!(
+			"Cannot wrap the return type `", stringify!($( $other )*), "`.\n\
+			 Help: only `-> [Self]` and `-> [Transition<Self, Next>]` can be wrapped."
+		));
+	};
+
+	(@WRAP_FN
+		[ $( $ret_ty: tt )* ]
+		$wrap: tt
 		{
-	        $( $item: tt )*
-        }
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+			[
+				$fn_vis: vis
+				[ $( $fn_type: ident )* ]
+				$fn_ident: ident
+				[ $( $gens: tt )* ]
+				( $( $params: tt )* )
+				[ $( $where_clause: tt )* ]
+			]
+			$self: ident
+			$args: tt
+		}
 	) => {
-		impl$(< $( $trait_gen )* >)?
-		$trait_ty
-		for $enum_ident<$( $enum_gen )*>
-		$( where $( $trait_bound )* )?
+	    $fn_vis
+	    $( $fn_type )*
+	    fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    -> $( $ret_ty )*
+	    where $( $where_clause )*
 		{
-			$(
-				$crate::enum_delegate_impls! {
-					@ITEM
-					$enum_ident
-					$enum_vars
-					$item
-				}
-			)*
+			match $self {
+			    $(
+			        Self::$var_ident(var) => { $wrap($crate::enum_delegate_impls!(@VIA $via var).$fn_ident $args) }
+			    )*
+		    }
 		}
 	};
-	
+
 	//------------------------------------------------------------------------------------------------------------------
 	// fn(self)
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[
@@ -276,7 +1725,7 @@ macro_rules! enum_delegate_impls {
     ) => {
 	    $crate::enum_delegate_impls! {
 		    @FN
-		    $enum_ident {
+		    $enum_ident $via {
 			    $( $var_ident ),*
 		    }
 		    
@@ -297,7 +1746,7 @@ macro_rules! enum_delegate_impls {
 	// fn(&self)
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[
@@ -313,7 +1762,7 @@ macro_rules! enum_delegate_impls {
     ) => {
 	    $crate::enum_delegate_impls! {
 		    @FN
-		    $enum_ident {
+		    $enum_ident $via {
 			    $( $var_ident ),*
 		    }
 		    
@@ -334,7 +1783,7 @@ macro_rules! enum_delegate_impls {
 	// fn(&mut self)
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[
@@ -350,7 +1799,7 @@ macro_rules! enum_delegate_impls {
     ) => {
 	    $crate::enum_delegate_impls! {
 		    @FN
-		    $enum_ident {
+		    $enum_ident $via {
 			    $( $var_ident ),*
 		    }
 		    
@@ -367,11 +1816,169 @@ macro_rules! enum_delegate_impls {
 	    }
     };
 	
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: Box<Self>)
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self: Box<Self> $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @TYPED_FN
+		    Box
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( self: Box<Self> $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: Pin<&mut Self>)
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self: Pin<&mut Self> $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @TYPED_FN
+		    Pin
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( self: Pin<&mut Self> $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: Rc<Self>)
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self: Rc<Self> $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @TYPED_FN
+		    Rc
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( self: Rc<Self> $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: Arc<Self>)
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self: Arc<Self> $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    $crate::enum_delegate_impls! {
+		    @TYPED_FN
+		    Arc
+		    $enum_vars
+		    [
+			    $fn_vis
+			    [ $( $( $fn_type )* )? ]
+			    $fn_ident
+			    [ $( $( $gens )* )? ]
+			    ( self: Arc<Self> $(, $arg_ident: $arg_ty )* )
+			    [ $( $ret_ty )? ]
+			    [ $( $( $where_clause )* )? ]
+		    ]
+		    self
+		    ($($arg_ident),*)
+	    }
+    };
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: T<Self>) <- INVALID, any other typed receiver
+	(@ITEM
+		$enum_ident: ident $enum_vars: tt
+
+		[
+		    $fn_vis: vis
+		    $( [$( $fn_type: ident )*] )?
+		    fn $fn_ident: ident
+		    $( < [ $( $gens: tt )* ] > )?
+		    ( self: $self_ty: ty $(, $arg_ident: ident: $arg_ty: ty )*  $(,)? )
+		    $( -> $ret_ty: ty )?
+		    $( where [ $( $where_clause: tt )* ] )?
+		    $(;)?
+		]
+    ) => {
+	    compile_error!(concat!(
+		    "Cannot delegate `", stringify!($fn_ident), "`, the receiver `self: ", stringify!($self_ty), "` is not supported.\n\
+		     Help: typed receivers must be written as `self: Box<Self>`, `self: Pin<&mut Self>`, `self: Rc<Self>` or `self: Arc<Self>`."
+	    ));
+    };
+
 	//------------------------------------------------------------------------------------------------------------------
 	// fn() <- INVALID
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[
@@ -403,7 +2010,8 @@ macro_rules! enum_delegate_impls {
 		    
 		    compile_error!(
 			    format!("Cannot delegate a impl function to an enum without a self parameter.\n\
-			     Help: add a (self | &self | &mut | self: T<Self>) parameter.\n\
+			     Help: add a (self | &self | &mut self | self: Box<Self> | self: Pin<&mut Self> | self: Rc<Self> | self: Arc<Self>) parameter,\n\
+			     or delegate it to the type of a variant with `[@default Variant]`.\n\
 			     {types} {name} {gens} {args} {ret} {clause}\n\
 			     => \n\
 			     {types} {name} {gens} {args_correct} {ret} {clause}"
@@ -412,10 +2020,277 @@ macro_rules! enum_delegate_impls {
 	    }
     };
 	
+	//------------------------------------------------------------------------------------------------------------------
+	// Splits `-> impl [bounds] where [..];` into the bounds and the where clause
+	(@IMPL_RET $fn_tt: tt [ $( $bound: tt )* ] $(;)?) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt [ $( $bound )* ] [] [ $( $bound )* ] }
+	};
+	
+	(@IMPL_RET $fn_tt: tt [ $( $bound: tt )* ] where [ $( $where_clause: tt )* ] $(;)?) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt [ $( $bound )* ] [ $( $where_clause )* ] [ $( $bound )* ] }
+	};
+	
+	(@IMPL_RET $fn_tt: tt [ $( $bound: tt )* ] $next: tt $( $rest: tt )*) => {
+		$crate::enum_delegate_impls! { @IMPL_RET $fn_tt [ $( $bound )* $next ] $( $rest )* }
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// The trait implemented by the dispatch enum, found by the first trait of the bounds which isn't a marker.
+	// The path before the trait is ignored.
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ Iterator $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_FN Iterator $fn_tt $bounds_tt $where_tt }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ DoubleEndedIterator $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_FN Iterator $fn_tt $bounds_tt $where_tt }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ ExactSizeIterator $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_FN Iterator $fn_tt $bounds_tt $where_tt }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ Future $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_FN Future $fn_tt $bounds_tt $where_tt }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ $( :: )? $segment: ident :: $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ + $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ $lifetime: lifetime $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ Send $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ Sync $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt $bounds_tt: tt $where_tt: tt [ Unpin $( $rest: tt )* ]) => {
+		$crate::enum_delegate_impls! { @IMPL_KIND $fn_tt $bounds_tt $where_tt [ $( $rest )* ] }
+	};
+	
+	(@IMPL_KIND $fn_tt: tt [ $( $bound: tt )* ] $where_tt: tt $other: tt) => {
+		compile_error!(concat!(
+			"Cannot delegate a method returning `impl ", stringify!($( $bound )*), "`.\n\
+			 Help: only `impl Iterator` and `impl Future` can be delegated, each variant returns a different type."
+		));
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Each variant returns a different type, which are unified by the local enum `__Dispatch`
+	(@IMPL_FN
+		$kind: ident
+		{
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+			[
+				$fn_vis: vis
+				[ $( $fn_type: ident )* ]
+				$fn_ident: ident
+				[ $( $gens: tt )* ]
+				( $( $params: tt )* )
+			]
+			$self: ident
+			$args: tt
+		}
+		[ $( $bound: tt )* ]
+		[ $( $where_clause: tt )* ]
+	) => {
+	    $fn_vis
+	    $( $fn_type )*
+	    fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    -> impl $( $bound )*
+	    where $( $where_clause )*
+		{
+			$crate::paste! {
+				enum __Dispatch<$( [<__V $var_ident>] ),*> {
+					$( $var_ident([<__V $var_ident>]) ),*
+				}
+			}
+			
+			$crate::enum_delegate_impls! {
+				@DISPATCH_IMPL
+				$kind { $( $var_ident ),* }
+			}
+			
+			match $self {
+			    $(
+			        Self::$var_ident(var) => { __Dispatch::$var_ident($crate::enum_delegate_impls!(@VIA $via var).$fn_ident $args) }
+			    )*
+		    }
+		}
+	};
+	
+	// The type of each variant is the parameter `__V{Variant}`, so that it can't shadow the names used in the impls
+	(@DISPATCH_IMPL
+		Iterator { $first: ident $(, $var_ident: ident )* }
+	) => {
+		$crate::paste! {
+			impl<[<__V $first>]: Iterator $(, [<__V $var_ident>]: Iterator<Item = <[<__V $first>] as Iterator>::Item> )*> Iterator
+			for __Dispatch<[<__V $first>] $(, [<__V $var_ident>] )*>
+			{
+				type Item = <[<__V $first>] as Iterator>::Item;
+			
+				fn next(&mut self) -> Option<Self::Item> {
+					match self {
+						__Dispatch::$first(iter) => iter.next(),
+						$( __Dispatch::$var_ident(iter) => iter.next(), )*
+					}
+				}
+			
+				fn size_hint(&self) -> (usize, Option<usize>) {
+					match self {
+						__Dispatch::$first(iter) => iter.size_hint(),
+						$( __Dispatch::$var_ident(iter) => iter.size_hint(), )*
+					}
+				}
+			}
+		
+			impl<[<__V $first>]: DoubleEndedIterator $(, [<__V $var_ident>]: DoubleEndedIterator<Item = <[<__V $first>] as Iterator>::Item> )*> 
+			DoubleEndedIterator for __Dispatch<[<__V $first>] $(, [<__V $var_ident>] )*>
+			{
+				fn next_back(&mut self) -> Option<Self::Item> {
+					match self {
+						__Dispatch::$first(iter) => iter.next_back(),
+						$( __Dispatch::$var_ident(iter) => iter.next_back(), )*
+					}
+				}
+			}
+		
+			impl<[<__V $first>]: ExactSizeIterator $(, [<__V $var_ident>]: ExactSizeIterator<Item = <[<__V $first>] as Iterator>::Item> )*> 
+			ExactSizeIterator for __Dispatch<[<__V $first>] $(, [<__V $var_ident>] )*> {}
+		}
+	};
+	
+	(@DISPATCH_IMPL
+		Future { $first: ident $(, $var_ident: ident )* }
+	) => {
+		$crate::paste! {
+			impl<[<__V $first>]: std::future::Future $(, [<__V $var_ident>]: std::future::Future<Output = <[<__V $first>] as std::future::Future>::Output> )*> 
+			std::future::Future for __Dispatch<[<__V $first>] $(, [<__V $var_ident>] )*>
+			{
+				type Output = <[<__V $first>] as std::future::Future>::Output;
+			
+				fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+					// SAFETY: the future is never moved out of the pinned enum
+					unsafe {
+						match self.get_unchecked_mut() {
+							__Dispatch::$first(future) => std::pin::Pin::new_unchecked(future).poll(cx),
+							$( __Dispatch::$var_ident(future) => std::pin::Pin::new_unchecked(future).poll(cx), )*
+						}
+					}
+				}
+			}
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// The match on each variant
+	(@MATCH [$( $fn_type: ident )*] $self: ident $via: tt { $( $var_ident: ident ),* } $fn_ident: ident $args: tt) => {
+		match $self {
+		    $(
+		        Self::$var_ident(var) => { $crate::enum_delegate_impls!(@VIA $via var).$fn_ident $args }
+		    )*
+	    }
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// fn(self: T<Self>), each variant is called through the same kind of receiver
+	(@TYPED_FN
+		$receiver: ident
+		{ [] $( $var_ident: ident $var_ty: tt ),* }
+		[
+			$fn_vis: vis
+			[ $( $fn_type: ident )* ]
+			$fn_ident: ident
+			[ $( $gens: tt )* ]
+			( $( $params: tt )* )
+			[ $( $ret_ty: ty )? ]
+			[ $( $where_clause: tt )* ]
+		]
+		$self: ident
+		$args: tt
+	) => {
+	    $fn_vis
+	    $( $fn_type )*
+	    fn $fn_ident
+	    < $( $gens )* >
+	    ( $( $params )* )
+	    $( -> $ret_ty )?
+	    where $( $where_clause )*
+		{
+			$crate::enum_delegate_impls!(@PROJECT $receiver $self { $( $var_ident ),* } $fn_ident $args)
+		}
+	};
+
+	(@TYPED_FN
+		$receiver: ident
+		{ [ $( $via: tt )* ] $( $var_ident: ident $var_ty: tt ),* }
+		[ $fn_vis: vis [ $( $fn_type: ident )* ] $fn_ident: ident $( $sig: tt )* ]
+		$self: ident
+		$args: tt
+	) => {
+		compile_error!(concat!(
+			"Cannot delegate `", stringify!($fn_ident), "` through `self: ", stringify!($receiver), "<Self>` ",
+			"via `", stringify!($( $via )*), "`, the field is not wrapped in the receiver's type."
+		));
+	};
+
+	// The box is moved out of, then the variant is boxed again
+	(@PROJECT Box $self: ident { $( $var_ident: ident ),* } $fn_ident: ident $args: tt) => {
+		match *$self {
+		    $(
+		        Self::$var_ident(var) => { std::boxed::Box::new(var).$fn_ident $args }
+		    )*
+	    }
+	};
+
+	(@PROJECT Pin $self: ident { $( $var_ident: ident ),* } $fn_ident: ident $args: tt) => {
+		// SAFETY: `@PIN_GUARD` rejects the `Unpin` and `Drop` impls that could move the variant out of the pinned enum,
+		// and the variant is not moved here
+		unsafe {
+			match $self.get_unchecked_mut() {
+			    $(
+			        Self::$var_ident(var) => { std::pin::Pin::new_unchecked(var).$fn_ident $args }
+			    )*
+		    }
+		}
+	};
+
+	// The enum is cloned only if the pointer is shared, then the variant is wrapped in a new, unshared pointer
+	(@PROJECT Rc $self: ident { $( $var_ident: ident ),* } $fn_ident: ident $args: tt) => {
+		match std::rc::Rc::unwrap_or_clone($self) {
+		    $(
+		        Self::$var_ident(var) => { std::rc::Rc::new(var).$fn_ident $args }
+		    )*
+	    }
+	};
+
+	(@PROJECT Arc $self: ident { $( $var_ident: ident ),* } $fn_ident: ident $args: tt) => {
+		match std::sync::Arc::unwrap_or_clone($self) {
+		    $(
+		        Self::$var_ident(var) => { std::sync::Arc::new(var).$fn_ident $args }
+		    )*
+	    }
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Expression to call the method on, either the variant or a field of it
+	(@VIA [ $( $via: tt )* ] $var: ident) => { $var $( $via )* };
+
 	//------------------------------------------------------------------------------------------------------------------
 	// fn(self)
 	(@FN
-		$enum_ident: ident {
+		$enum_ident: ident $via: tt {
 		    $( $var_ident: ident ),*
 	    }
 	
@@ -438,18 +2313,14 @@ macro_rules! enum_delegate_impls {
 	    $( -> $ret_ty )?
 	    $( where $( $where_clause )* )?
 		{
-			match self {
-			    $(
-			        Self::$var_ident(var) => { var.$fn_ident $args }
-			    )*
-		    }
+			$crate::enum_delegate_impls!(@MATCH [$( $( $fn_type )* )?] self $via { $( $var_ident ),* } $fn_ident $args)
 		}
     };
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// fn(&self)
 	(@FN
-		$enum_ident: ident {
+		$enum_ident: ident $via: tt {
 		    $( $var_ident: ident ),*
 	    }
 	
@@ -469,21 +2340,17 @@ macro_rules! enum_delegate_impls {
 		fn $fn_ident
 	    $( <$( $gens )*> )?
 	    ( &self $(, $arg_ident: $arg_ty )* )
-	    $( -> $ret_ty )?
-	    $( where $( $where_clause )* )?
-		{
-			match self {
-			    $(
-			        Self::$var_ident(var) => { var.$fn_ident $args }
-			    )*
-		    }
+	    $( -> $ret_ty )?
+	    $( where $( $where_clause )* )?
+		{
+			$crate::enum_delegate_impls!(@MATCH [$( $( $fn_type )* )?] self $via { $( $var_ident ),* } $fn_ident $args)
 		}
     };
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// fn(&mut self)
 	(@FN
-		$enum_ident: ident {
+		$enum_ident: ident $via: tt {
 		    $( $var_ident: ident ),*
 	    }
 	
@@ -506,11 +2373,7 @@ macro_rules! enum_delegate_impls {
 	    $( -> $ret_ty )?
 	    $( where $( $where_clause )* )?
 		{
-			match self {
-			    $(
-			        Self::$var_ident(var) => { var.$fn_ident $args }
-			    )*
-		    }
+			$crate::enum_delegate_impls!(@MATCH [$( $( $fn_type )* )?] self $via { $( $var_ident ),* } $fn_ident $args)
 		}
     };
 	
@@ -518,7 +2381,7 @@ macro_rules! enum_delegate_impls {
 	// const
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[const $($token: tt)*]
@@ -530,7 +2393,7 @@ macro_rules! enum_delegate_impls {
 	// type
 	(@ITEM
 		$enum_ident: ident {
-		    $( $var_ident: ident ),*
+		    $via: tt $( $var_ident: ident $var_ty: tt ),*
 	    }
 	
 		[type $($token: tt)*]
@@ -1148,4 +3011,551 @@ mod test_generics_many_with_trait_generics {
 			todo!()
 		}
 	}
-}
\ No newline at end of file
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_async_and_impl_trait {
+	use std::future::Future;
+	use std::pin::pin;
+	use std::task::{Context, Poll, Waker};
+
+	pub struct Countdown(u32);
+	pub struct Repeat(u32, u32);
+
+	trait Source {
+		fn values(&self) -> impl Iterator<Item = u32> + '_;
+
+		fn doubled(&self) -> impl Send + DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_;
+
+		fn total(&self) -> impl Future<Output = u32>;
+
+		async fn first(&self) -> u32;
+	}
+
+	impl Source for Countdown {
+		fn values(&self) -> impl Iterator<Item = u32> + '_ { (0..self.0).rev() }
+
+		fn doubled(&self) -> impl Send + DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_ {
+			(0..self.0).map(|value| value * 2)
+		}
+
+		fn total(&self) -> impl Future<Output = u32> { std::future::ready((0..self.0).sum()) }
+
+		async fn first(&self) -> u32 { self.0 }
+	}
+
+	impl Source for Repeat {
+		fn values(&self) -> impl Iterator<Item = u32> + '_ { std::iter::repeat_n(self.0, self.1 as usize) }
+
+		fn doubled(&self) -> impl Send + DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_ {
+			std::iter::repeat_n(self.0 * 2, self.1 as usize)
+		}
+
+		fn total(&self) -> impl Future<Output = u32> {
+			let total = self.0 * self.1;
+			async move { total }
+		}
+
+		async fn first(&self) -> u32 { self.0 }
+	}
+
+	impl Countdown {
+		fn len(&self) -> u32 { self.0 }
+	}
+
+	impl Repeat {
+		fn len(&self) -> u32 { self.1 }
+	}
+
+	pub enum Sources {
+		Countdown(Countdown),
+		Repeat(Repeat),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Sources {
+				Countdown(Countdown),
+				Repeat(Repeat),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Source {
+				[fn values(&self) -> impl Iterator<Item = u32> + '_]
+				[fn doubled(&self) -> impl Send + DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_]
+				[fn total(&self) -> impl std::future::Future<Output = u32>]
+				[async fn first(&self) -> u32]
+			}
+
+			impl {
+				[[async] fn len(&self) -> u32]
+			}
+		}
+	}
+
+	// Variants named like the items used by the local enum `__Dispatch`
+	pub enum Shadowing {
+		Option(Countdown),
+		Iterator(Repeat),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Shadowing {
+				Option(Countdown),
+				Iterator(Repeat),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Source {
+				[fn values(&self) -> impl Iterator<Item = u32> + '_]
+				[fn doubled(&self) -> impl Send + DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_]
+				[fn total(&self) -> impl std::future::Future<Output = u32>]
+				[async fn first(&self) -> u32]
+			}
+		}
+	}
+
+	fn block_on<F: Future>(future: F) -> F::Output {
+		let mut future = pin!(future);
+		let mut cx = Context::from_waker(Waker::noop());
+
+		loop {
+			if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+				return output;
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		let countdown = Sources::Countdown(Countdown(3));
+		let repeat = Sources::Repeat(Repeat(2, 2));
+
+		assert_eq!(countdown.values().collect::<Vec<_>>(), [2, 1, 0]);
+		assert_eq!(repeat.values().size_hint(), (2, Some(2)));
+		assert_eq!(block_on(countdown.total()), 3);
+		assert_eq!(block_on(repeat.total()), 4);
+		assert_eq!(block_on(repeat.first()), 2);
+
+		assert_eq!(countdown.doubled().rev().collect::<Vec<_>>(), [4, 2, 0]);
+		assert_eq!(repeat.doubled().len(), 2);
+		assert_eq!(block_on(countdown.len()), 3);
+	}
+
+	#[test]
+	fn shadowing_variants() {
+		let countdown = Shadowing::Option(Countdown(3));
+		let repeat = Shadowing::Iterator(Repeat(2, 2));
+
+		assert_eq!(countdown.values().collect::<Vec<_>>(), [2, 1, 0]);
+		assert_eq!(repeat.doubled().rev().collect::<Vec<_>>(), [4, 4]);
+		assert_eq!(block_on(repeat.total()), 4);
+	}
+}
+
+#[cfg(test)]
+mod test_typed_receivers {
+	use std::marker::PhantomPinned;
+	use std::pin::Pin;
+	use std::rc::Rc;
+	use std::sync::Arc;
+
+	#[derive(Clone)]
+	pub struct Move(u32);
+	#[derive(Clone)]
+	pub struct Wait(u32);
+
+	trait Command {
+		fn run(self: Box<Self>) -> u32;
+
+		fn step(self: Pin<&mut Self>, by: u32) -> u32;
+
+		fn shared(self: Rc<Self>) -> u32;
+
+		fn sent(self: Arc<Self>) -> u32;
+	}
+
+	impl Command for Move {
+		fn run(self: Box<Self>) -> u32 { self.0 }
+
+		fn step(mut self: Pin<&mut Self>, by: u32) -> u32 {
+			self.0 += by;
+			self.0
+		}
+
+		fn shared(self: Rc<Self>) -> u32 { self.0 + Rc::strong_count(&self) as u32 }
+
+		fn sent(self: Arc<Self>) -> u32 { self.0 }
+	}
+
+	impl Command for Wait {
+		fn run(self: Box<Self>) -> u32 { 0 }
+
+		fn step(mut self: Pin<&mut Self>, by: u32) -> u32 {
+			self.0 = self.0.saturating_sub(by);
+			self.0
+		}
+
+		fn shared(self: Rc<Self>) -> u32 { 0 }
+
+		fn sent(self: Arc<Self>) -> u32 { 0 }
+	}
+
+	#[derive(Clone)]
+	pub enum Commands {
+		Move(Move),
+		Wait(Wait),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Commands {
+				Move(Move),
+				Wait(Wait),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Command {
+				[fn run(self: Box<Self>) -> u32]
+				[fn step(self: Pin<&mut Self>, by: u32) -> u32]
+				[fn shared(self: Rc<Self>) -> u32]
+				[fn sent(self: Arc<Self>) -> u32]
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		assert_eq!(Box::new(Commands::Move(Move(3))).run(), 3);
+		assert_eq!(Box::new(Commands::Wait(Wait(3))).run(), 0);
+
+		let mut wait = Commands::Wait(Wait(5));
+		assert_eq!(Pin::new(&mut wait).step(2), 3);
+		assert!(matches!(wait, Commands::Wait(Wait(3))));
+
+		let shared = Rc::new(Commands::Move(Move(1)));
+		let other = Rc::clone(&shared);
+		assert_eq!(shared.shared(), 2);
+		assert_eq!(other.shared(), 2);
+
+		assert_eq!(Arc::new(Commands::Move(Move(4))).sent(), 4);
+	}
+
+	pub struct Anchored(u32, PhantomPinned);
+
+	trait Counter {
+		fn bump(self: Pin<&mut Self>) -> u32;
+	}
+
+	impl Counter for Anchored {
+		fn bump(self: Pin<&mut Self>) -> u32 {
+			// SAFETY: the counter is not structurally pinned
+			let count = unsafe { &mut self.get_unchecked_mut().0 };
+			*count += 1;
+			*count
+		}
+	}
+
+	impl Counter for Move {
+		fn bump(mut self: Pin<&mut Self>) -> u32 {
+			self.0 += 1;
+			self.0
+		}
+	}
+
+	pub enum Counters {
+		Anchored(Anchored),
+		Move(Move),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Counters {
+				Anchored(Anchored),
+				Move(Move),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Counter {
+				[fn bump(self: Pin<&mut Self>) -> u32]
+			}
+		}
+	}
+
+	#[test]
+	fn pinned() {
+		let mut anchored = std::pin::pin!(Counters::Anchored(Anchored(0, PhantomPinned)));
+		assert_eq!(anchored.as_mut().bump(), 1);
+		assert_eq!(anchored.as_mut().bump(), 2);
+
+		let mut moving = std::pin::pin!(Counters::Move(Move(7)));
+		assert_eq!(moving.as_mut().bump(), 8);
+	}
+}
+
+#[cfg(test)]
+mod test_wrapped_returns {
+	use crate::Transition::{self, ChangedTo, Unchanged};
+
+	pub struct Idle(u32);
+	pub struct Busy(u32);
+
+	trait Tick: Sized {
+		fn tick(self, delta: u32) -> Transition<Self, Worker>;
+
+		fn reset(&self) -> Self;
+	}
+
+	impl Tick for Idle {
+		fn tick(self, delta: u32) -> Transition<Self, Worker> {
+			if self.0 + delta > 3 { ChangedTo(Busy(0).into()) } else { Unchanged(Idle(self.0 + delta)) }
+		}
+
+		fn reset(&self) -> Self { Idle(0) }
+	}
+
+	impl Tick for Busy {
+		fn tick(self, delta: u32) -> Transition<Self, Worker> { Unchanged(Busy(self.0 + delta)) }
+
+		fn reset(&self) -> Self { Busy(0) }
+	}
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			pub enum Worker {
+				Idle(Idle),
+				Busy(Busy),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Tick {
+				[fn tick(self, delta: u32) -> [crate::Transition<Self, Worker>]]
+				[fn reset(&self) -> [Self]]
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		let Unchanged(Worker::Idle(Idle(2))) = Worker::Idle(Idle(0)).tick(2)
+		else { panic!("expected `Unchanged(Idle(2))`") };
+
+		let ChangedTo(Worker::Busy(Busy(0))) = Worker::Idle(Idle(2)).tick(2)
+		else { panic!("expected `ChangedTo(Busy(0))`") };
+
+		let Unchanged(Worker::Busy(Busy(5))) = Worker::Busy(Busy(4)).tick(1)
+		else { panic!("expected `Unchanged(Busy(5))`") };
+
+		assert!(matches!(Worker::Busy(Busy(4)).reset(), Worker::Busy(Busy(0))));
+	}
+}
+
+#[cfg(test)]
+mod test_associated_items {
+	#[derive(Default)]
+	pub struct Idle(u32);
+	pub struct Busy(u32, u32);
+
+	impl Busy {
+		pub fn new(first: u32, second: u32) -> Self { Busy(first, second) }
+	}
+
+	trait Footprint {
+		const SIZE: usize;
+		const SLOTS: u32;
+
+		fn name() -> &'static str;
+	}
+
+	impl Footprint for Idle {
+		const SIZE: usize = size_of::<Self>();
+		const SLOTS: u32 = 1;
+
+		fn name() -> &'static str { "idle" }
+	}
+
+	impl Footprint for Busy {
+		const SIZE: usize = size_of::<Self>();
+		const SLOTS: u32 = 2;
+
+		fn name() -> &'static str { "busy" }
+	}
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			pub enum Worker {
+				Idle(Idle),
+				Busy(Busy),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Default {
+				[[@default Idle] fn default() -> Self]
+			}
+
+			impl trait Footprint {
+				[[@max] const SIZE: usize]
+				[[@sum] const SLOTS: u32]
+				[[@default Busy] fn name() -> &'static str]
+			}
+
+			impl {
+				[[@default Busy] pub fn new(first: u32, second: u32) -> Self]
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		assert!(matches!(Worker::default(), Worker::Idle(Idle(0))));
+		assert!(matches!(Worker::new(1, 2), Worker::Busy(Busy(1, 2))));
+		assert_eq!(Worker::name(), "busy");
+
+		assert_eq!(<Worker as Footprint>::SIZE, size_of::<Busy>());
+		assert_eq!(<Worker as Footprint>::SLOTS, 3);
+	}
+}
+
+#[cfg(test)]
+mod test_fallbacks {
+	pub struct Walking(u32);
+	pub struct Frozen;
+
+	trait Tick {
+		fn tick(&mut self, delta: u32) -> u32;
+
+		fn describe(self, prefix: &str) -> String;
+	}
+
+	impl Tick for Walking {
+		fn tick(&mut self, delta: u32) -> u32 {
+			self.0 += delta;
+			self.0
+		}
+
+		fn describe(self, prefix: &str) -> String { format!("{prefix}walking {}", self.0) }
+	}
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			pub enum Mover {
+				Walking(Walking),
+				Frozen(Frozen),
+			}
+		}
+
+		DELEGATES: {
+			[@skip Frozen] impl trait Tick {
+				[fn tick(&mut self, delta: u32) -> u32 else { 0 }]
+				[fn describe(self, prefix: &str) -> String else { format!("{prefix}{}", self.name()) }]
+			}
+		}
+	}
+
+	impl Mover {
+		fn name(&self) -> &'static str {
+			match self {
+				Mover::Walking(_) => "walking",
+				Mover::Frozen(_) => "frozen",
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut walking = Mover::Walking(Walking(1));
+		assert_eq!(walking.tick(2), 3);
+		assert_eq!(walking.describe("> "), "> walking 3");
+
+		let mut frozen = Mover::Frozen(Frozen);
+		assert_eq!(frozen.tick(2), 0);
+		assert_eq!(frozen.describe("> "), "> frozen");
+	}
+}
+
+#[cfg(test)]
+mod test_projection {
+	use crate::delegatable_trait;
+
+	pub struct State<T> {
+		name: &'static str,
+		inner: Inner<T>,
+	}
+
+	pub struct Inner<T>(T);
+
+	pub struct Walking(u32);
+	pub struct Frozen;
+
+	delegatable_trait! {
+		pub trait Speed {
+			[fn speed(&self) -> u32]
+		}
+	}
+
+	pub trait Tick {
+		fn tick(&mut self, delta: u32) -> u32;
+	}
+
+	impl Speed for Walking {
+		fn speed(&self) -> u32 { self.0 }
+	}
+
+	impl Speed for Frozen {
+		fn speed(&self) -> u32 { 0 }
+	}
+
+	impl Tick for Walking {
+		fn tick(&mut self, delta: u32) -> u32 { self.0 * delta }
+	}
+
+	impl Tick for Frozen {
+		fn tick(&mut self, _delta: u32) -> u32 { 0 }
+	}
+
+	impl<T> State<T> {
+		fn label(&self) -> &'static str { self.name }
+	}
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			pub enum Mover {
+				Walking(State<Walking>),
+				Frozen(State<Frozen>),
+			}
+		}
+
+		DELEGATES: {
+			impl trait Speed via .inner.0;
+
+			impl trait Tick via .inner.0 {
+				[fn tick(&mut self, delta: u32) -> u32]
+			}
+
+			impl {
+				[fn label(&self) -> &'static str]
+			}
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut walking = Mover::Walking(State { name: "walking", inner: Inner(Walking(3)) });
+		assert_eq!(walking.speed(), 3);
+		assert_eq!(walking.tick(2), 6);
+		assert_eq!(walking.label(), "walking");
+
+		let mut frozen = Mover::Frozen(State { name: "frozen", inner: Inner(Frozen) });
+		assert_eq!(frozen.speed(), 0);
+		assert_eq!(frozen.tick(2), 0);
+		assert_eq!(frozen.label(), "frozen");
+	}
+}
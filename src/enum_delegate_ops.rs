@@ -0,0 +1,591 @@
+/// Implements an operator trait for an enum, used by the `impl operator` delegations of
+/// [enum_delegate_impls!](crate::enum_delegate_impls).
+///
+/// Binary operators are applied to pairs of the same variant,
+/// the `else` policy decides what happens when the operands are different variants.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! enum_delegate_ops {
+	//------------------------------------------------------------------------------------------------------------------
+	// Index<Idx> -> Output, the output is the same for every variant
+	(
+		$enum_tt: tt
+		[ $( <[ $( $impl_gen: tt )* ]> )? ]
+
+		$op: ident < $idx_ty: ty > -> $out_ty: ty
+		$( where [ $( $op_bound: tt )* ] )?
+	) => {
+		$crate::enum_delegate_ops! {
+			@INDEX
+			$enum_tt
+			{ $( $( $impl_gen )* )? }
+			{ $( $( $op_bound )* )? }
+			$op [ $idx_ty ] [ $out_ty ]
+		}
+	};
+
+	(
+		$enum_tt: tt
+		[ $( <[ $( $impl_gen: tt )* ]> )? ]
+
+		$op: ident
+		$( where [ $( $op_bound: tt )* ] )?
+		$( else $policy: ident )?
+	) => {
+		$crate::enum_delegate_ops! {
+			@OP
+			$enum_tt
+			{ $( $( $impl_gen )* )? }
+			{ $( $( $op_bound )* )? }
+			$op [ $( $policy )? ]
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Operator name to its trait family, the default policy is filled in
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Add $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [Add add] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Sub $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [Sub sub] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Mul $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [Mul mul] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Div $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [Div div] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Rem $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [Rem rem] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt BitAnd $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [BitAnd bitand] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt BitOr $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [BitOr bitor] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt BitXor $policy_tt: tt) => {
+		$crate::enum_delegate_ops! { @BINARY $enum_tt $gen_tt $bound_tt [BitXor bitxor] $policy_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Neg []) => {
+		$crate::enum_delegate_ops! { @UNARY $enum_tt $gen_tt $bound_tt [Neg neg] }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Not []) => {
+		$crate::enum_delegate_ops! { @UNARY $enum_tt $gen_tt $bound_tt [Not not] }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt PartialEq []) => {
+		$crate::enum_delegate_ops! { @PARTIAL_EQ $enum_tt $gen_tt $bound_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Eq []) => {
+		$crate::enum_delegate_ops! { @EQ $enum_tt $gen_tt $bound_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Ord []) => {
+		$crate::enum_delegate_ops! { @ORD $enum_tt $gen_tt $bound_tt [discriminant] }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Ord [ $policy: ident ]) => {
+		$crate::enum_delegate_ops! { @ORD $enum_tt $gen_tt $bound_tt [ $policy ] }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt Hash []) => {
+		$crate::enum_delegate_ops! { @HASH $enum_tt $gen_tt $bound_tt }
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt $op: ident [ $policy: ident ]) => {
+		compile_error!(concat!(
+			"`", stringify!($op), "` does not take a mismatch policy, found `else ", stringify!($policy), "`.\n\
+			 Help: only binary operators, `PartialOrd` and `Ord` compare two variants."
+		));
+	};
+
+	(@OP $enum_tt: tt $gen_tt: tt $bound_tt: tt $op: ident $policy_tt: tt) => {
+		compile_error!(concat!(
+			"Unsupported operator `", stringify!($op), "`.\n\
+			 Help: supported operators are `Add`, `Sub`, `Mul`, `Div`, `Rem`, `BitAnd`, `BitOr`, `BitXor`, \
+			 `Neg`, `Not`, `Index`, `IndexMut`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`."
+		));
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Binary operators, `else panic` (default) or `else None`, which makes the output `Option<Self>`
+	(@BINARY
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		[ $op: ident $op_fn: ident ]
+		[ $( panic )? ]
+	) => {
+		impl<$( $impl_gen )*> ::core::ops::$op for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			type Output = Self;
+
+			#[allow(unreachable_patterns)]
+			fn $op_fn(self, rhs: Self) -> Self {
+				match (self, rhs) {
+					$(
+						(Self::$var_ident(lhs), Self::$var_ident(rhs)) => Self::$var_ident(::core::ops::$op::$op_fn(lhs, rhs)),
+					)*
+					_ => panic!(concat!("`", stringify!($op), "` on different variants of `", stringify!($enum_ident), "`")),
+				}
+			}
+		}
+	};
+
+	(@BINARY
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		[ $op: ident $op_fn: ident ]
+		[ None ]
+	) => {
+		impl<$( $impl_gen )*> ::core::ops::$op for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			type Output = Option<Self>;
+
+			#[allow(unreachable_patterns)]
+			fn $op_fn(self, rhs: Self) -> Option<Self> {
+				match (self, rhs) {
+					$(
+						(Self::$var_ident(lhs), Self::$var_ident(rhs)) => Some(Self::$var_ident(::core::ops::$op::$op_fn(lhs, rhs))),
+					)*
+					_ => None,
+				}
+			}
+		}
+	};
+
+	(@BINARY $enum_tt: tt $gen_tt: tt $bound_tt: tt [ $op: ident $op_fn: ident ] [ $policy: ident ]) => {
+		compile_error!(concat!(
+			"Unknown mismatch policy `", stringify!($policy), "` for `", stringify!($op), "`.\n\
+			 Help: expected `else panic` or `else None`."
+		));
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@UNARY
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		[ $op: ident $op_fn: ident ]
+	) => {
+		impl<$( $impl_gen )*> ::core::ops::$op for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			type Output = Self;
+
+			fn $op_fn(self) -> Self {
+				match self {
+					$( Self::$var_ident(var) => Self::$var_ident(::core::ops::$op::$op_fn(var)) ),*
+				}
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@INDEX
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		Index [ $idx_ty: ty ] [ $out_ty: ty ]
+	) => {
+		impl<$( $impl_gen )*> ::core::ops::Index<$idx_ty> for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			type Output = $out_ty;
+
+			fn index(&self, index: $idx_ty) -> &$out_ty {
+				match self {
+					$( Self::$var_ident(var) => ::core::ops::Index::index(var, index) ),*
+				}
+			}
+		}
+	};
+
+	(@INDEX
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		IndexMut [ $idx_ty: ty ] [ $out_ty: ty ]
+	) => {
+		impl<$( $impl_gen )*> ::core::ops::IndexMut<$idx_ty> for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			fn index_mut(&mut self, index: $idx_ty) -> &mut $out_ty {
+				match self {
+					$( Self::$var_ident(var) => ::core::ops::IndexMut::index_mut(var, index) ),*
+				}
+			}
+		}
+	};
+
+	(@INDEX $enum_tt: tt $gen_tt: tt $bound_tt: tt $op: ident $idx_tt: tt $out_tt: tt) => {
+		compile_error!(concat!(
+			"Unsupported operator `", stringify!($op), "` with an index and an output.\n\
+			 Help: only `Index<Idx> -> Output` and `IndexMut<Idx> -> Output` are declared this way."
+		));
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Comparisons, different variants are never equal
+	(@PARTIAL_EQ
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+	) => {
+		impl<$( $impl_gen )*> ::core::cmp::PartialEq for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			#[allow(unreachable_patterns)]
+			fn eq(&self, other: &Self) -> bool {
+				match (self, other) {
+					$(
+						(Self::$var_ident(lhs), Self::$var_ident(rhs)) => ::core::cmp::PartialEq::eq(lhs, rhs),
+					)*
+					_ => false,
+				}
+			}
+		}
+	};
+
+	(@EQ
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+	) => {
+		impl<$( $impl_gen )*> ::core::cmp::Eq for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// `PartialOrd`, `[Ord]` if `Ord` is delegated too
+	(@PARTIAL_ORD_WITH $enum_tt: tt [] { $gen_tt: tt $bound_tt: tt [] }) => {
+		$crate::enum_delegate_ops! { @PARTIAL_ORD $enum_tt $gen_tt $bound_tt [discriminant] }
+	};
+
+	(@PARTIAL_ORD_WITH $enum_tt: tt [] { $gen_tt: tt $bound_tt: tt [ $policy: ident ] }) => {
+		$crate::enum_delegate_ops! { @PARTIAL_ORD $enum_tt $gen_tt $bound_tt [ $policy ] }
+	};
+
+	(@PARTIAL_ORD_WITH $enum_tt: tt [Ord] { $gen_tt: tt $bound_tt: tt [] }) => {
+		$crate::enum_delegate_ops! { @PARTIAL_ORD_CANONICAL $enum_tt $gen_tt $bound_tt }
+	};
+
+	(@PARTIAL_ORD_WITH $enum_tt: tt [Ord] { $gen_tt: tt $bound_tt: tt [ $policy: ident ] }) => {
+		compile_error!(concat!(
+			"`PartialOrd` does not take a mismatch policy when `Ord` is delegated too, found `else ", stringify!($policy), "`.\n\
+			 Help: `PartialOrd` is implemented as `Some(self.cmp(other))`, place the policy on `Ord`."
+		));
+	};
+
+	// Consistent with `Ord` by construction
+	(@PARTIAL_ORD_CANONICAL
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			$enum_vars: tt
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+	) => {
+		impl<$( $impl_gen )*> ::core::cmp::PartialOrd for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+	};
+
+	// `else discriminant` (default), `else None` or `else panic`
+	(@PARTIAL_ORD
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		[ $policy: ident ]
+	) => {
+		impl<$( $impl_gen )*> ::core::cmp::PartialOrd for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			#[allow(unreachable_patterns)]
+			fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+				match (self, other) {
+					$(
+						(Self::$var_ident(lhs), Self::$var_ident(rhs)) => ::core::cmp::PartialOrd::partial_cmp(lhs, rhs),
+					)*
+					_ => $crate::enum_delegate_ops!(@MISMATCH PartialOrd $policy $enum_ident { $( $var_ident ),* } self other),
+				}
+			}
+		}
+	};
+
+	// `else discriminant` (default) or `else panic`
+	(@ORD
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+		[ $policy: ident ]
+	) => {
+		impl<$( $impl_gen )*> ::core::cmp::Ord for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			#[allow(unreachable_patterns)]
+			fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+				match (self, other) {
+					$(
+						(Self::$var_ident(lhs), Self::$var_ident(rhs)) => ::core::cmp::Ord::cmp(lhs, rhs),
+					)*
+					_ => $crate::enum_delegate_ops!(@MISMATCH Ord $policy $enum_ident { $( $var_ident ),* } self other),
+				}
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Result of comparing different variants
+	(@MISMATCH PartialOrd None $enum_ident: ident $vars: tt $lhs: ident $rhs: ident) => { None };
+
+	(@MISMATCH PartialOrd discriminant $enum_ident: ident $vars: tt $lhs: ident $rhs: ident) => {
+		Some($crate::enum_delegate_ops!(@MISMATCH Ord discriminant $enum_ident $vars $lhs $rhs))
+	};
+
+	// The variants are ordered by declaration
+	(@MISMATCH $op: ident discriminant $enum_ident: ident { $( $var_ident: ident ),* } $lhs: ident $rhs: ident) => {{
+		#[derive(PartialEq, Eq, PartialOrd, Ord)]
+		enum Order { $( $var_ident ),* }
+
+		let order = |value: &Self| match value {
+			$( Self::$var_ident(_) => Order::$var_ident ),*
+		};
+
+		::core::cmp::Ord::cmp(&order($lhs), &order($rhs))
+	}};
+
+	(@MISMATCH $op: ident panic $enum_ident: ident $vars: tt $lhs: ident $rhs: ident) => {
+		panic!(concat!("`", stringify!($op), "` on different variants of `", stringify!($enum_ident), "`"))
+	};
+
+	(@MISMATCH $op: ident $policy: ident $enum_ident: ident $vars: tt $lhs: ident $rhs: ident) => {
+		compile_error!(concat!(
+			"Unknown mismatch policy `", stringify!($policy), "` for `", stringify!($op), "`.\n\
+			 Help: expected `else None` (`PartialOrd` only), `else discriminant` or `else panic`."
+		))
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// The discriminant is hashed first, so that equal values of different variants hash differently
+	(@HASH
+		[
+			$generics_tt: tt
+			$bounds_tt: tt
+			$enum_ident: ident
+			{ $via: tt $( $var_ident: ident $var_ty: tt ),* }
+		]
+		{ $( $impl_gen: tt )* }
+		{ $( $op_bound: tt )* }
+	) => {
+		impl<$( $impl_gen )*> ::core::hash::Hash for $crate::enum_delegate_ops!(@SELF $generics_tt $enum_ident)
+		where $( $op_bound )*
+		{
+			fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+				::core::hash::Hash::hash(&::core::mem::discriminant(self), state);
+
+				match self {
+					$( Self::$var_ident(var) => ::core::hash::Hash::hash(var, state) ),*
+				}
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@SELF { $( $enum_gen: tt )* } $enum_ident: ident) => { $enum_ident<$( $enum_gen )*> };
+}
+
+#[cfg(test)]
+mod test {
+	use std::collections::HashSet;
+
+	#[derive(Debug, Clone, Copy)]
+	pub enum Integer {
+		Int(i32),
+		UInt(u32),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Integer {
+				Int(i32),
+				UInt(u32),
+			}
+		}
+
+		DELEGATES: {
+			impl operator Add;
+			impl operator Mul else None;
+			impl operator PartialEq;
+			impl operator Eq;
+			impl operator PartialOrd;
+			impl operator Ord;
+			impl operator Hash;
+		}
+	}
+
+	#[derive(Debug)]
+	pub enum Signed<T> {
+		Int(i32),
+		List(Vec<T>),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Signed<[T]> {
+				Int(i32),
+				List(Vec<T>),
+			}
+		}
+
+		DELEGATES: {
+			impl<[T]> operator PartialEq where [T: PartialEq];
+			impl<[T]> operator PartialOrd where [T: PartialOrd] else None;
+		}
+	}
+
+	#[derive(Debug)]
+	pub enum Bits {
+		Byte([u8; 2]),
+		Word([u8; 4]),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Bits {
+				Byte([u8; 2]),
+				Word([u8; 4]),
+			}
+		}
+
+		DELEGATES: {
+			impl operator Index<usize> -> u8;
+			impl operator IndexMut<usize> -> u8;
+		}
+	}
+
+	#[derive(Debug, PartialEq)]
+	pub enum Float {
+		Single(f32),
+		Double(f64),
+	}
+
+	crate::enum_delegate_impls! {
+		ENUM_IN: {
+			Float {
+				Single(f32),
+				Double(f64),
+			}
+		}
+
+		DELEGATES: {
+			impl operator Neg;
+			impl operator Sub else panic;
+			impl operator PartialOrd;
+		}
+	}
+
+	#[test]
+	fn arithmetic() {
+		assert_eq!(Integer::Int(2) + Integer::Int(3), Integer::Int(5));
+		assert_eq!(Integer::UInt(2) * Integer::UInt(3), Some(Integer::UInt(6)));
+		assert_eq!(Integer::UInt(2) * Integer::Int(3), None);
+
+		assert_eq!(-Float::Single(1.5), Float::Single(-1.5));
+		assert_eq!(Float::Double(3.0) - Float::Double(1.0), Float::Double(2.0));
+	}
+
+	#[test]
+	#[should_panic(expected = "`Add` on different variants of `Integer`")]
+	fn mismatch() { let _ = Integer::Int(1) + Integer::UInt(1); }
+
+	#[test]
+	fn comparison() {
+		assert_ne!(Integer::Int(1), Integer::UInt(1));
+		assert!(Integer::Int(5) < Integer::Int(6));
+		assert!(Integer::Int(5) < Integer::UInt(0));
+		assert_eq!(Integer::UInt(0).max(Integer::Int(9)), Integer::UInt(0));
+		assert_eq!(Integer::Int(1).partial_cmp(&Integer::UInt(0)), Some(Integer::Int(1).cmp(&Integer::UInt(0))));
+
+		let set = HashSet::from([Integer::Int(1), Integer::UInt(1), Integer::Int(1)]);
+		assert_eq!(set.len(), 2);
+
+		assert!(Signed::<u8>::List(vec![1]) < Signed::List(vec![2]));
+		assert_eq!(Signed::<u8>::Int(1).partial_cmp(&Signed::List(vec![])), None);
+		assert!(Float::Single(1.0) < Float::Double(0.0));
+	}
+
+	#[test]
+	fn index() {
+		let mut bits = Bits::Word([1, 2, 3, 4]);
+		bits[3] = 9;
+		assert_eq!(bits[3], 9);
+		assert_eq!(Bits::Byte([5, 6])[1], 6);
+	}
+}
@@ -0,0 +1,222 @@
+/// Generates a fieldless companion of a data-carrying enum, with one unit variant per variant.
+///
+/// Opt-in through the `KIND` section of [delegated_enum!](crate::delegated_enum), 
+/// [extract_variants_into_enum!](crate::extract_variants_into_enum) (with `ENUM_OUT`),
+/// [type_state_enum!](crate::type_state_enum) and [type_value_table!](crate::type_value_table) (with `ENUM_OUT`),
+/// nothing is generated without it.
+///
+/// # Generates:
+/// - `[kind_ident]`, deriving `Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord`
+/// - `[kind_ident]::ALL`: all the kinds, in declaration order
+/// - `[kind_ident]::name()`: the name of the variant
+/// - `[enum_ident]::kind()`
+/// - From<&Enum> for Kind
+///
+/// The kind can also be used as the key of an [enum_variants_table!](crate::enum_variants_table),
+/// without listing the variants again: `enum_variants_table! { KIND: StateEnumKind; TABLE: { ... } }`.
+/// The kind must be in scope (importing the kind is enough), since the variants are stored in a macro with the same name.
+///
+/// # Input
+///
+/// ```pseudo
+/// [enum_vis] enum [enum_ident]<[generics]> [where [bounds]] {
+///     [var_ident]([var_type]),
+/// }
+///
+/// KIND: {
+///     [kind_attributes]
+///     [kind_vis] enum [kind_ident];
+/// }
+/// ```
+///
+/// The enum has the same syntax as [enum_variants_convert!](crate::enum_variants_convert).
+/// `[kind_attributes]` are applied on the kind, after the derives listed above.
+///
+/// # Example
+///
+/// ```rust
+/// use declarative_type_state::enum_kind;
+///
+/// pub enum Shape {
+///     Circle(f32),
+///     Square(f32),
+/// }
+///
+/// enum_kind! {
+///     pub enum Shape {
+///         Circle(f32),
+///         Square(f32),
+///     }
+///
+///     KIND: {
+///         pub enum ShapeKind;
+///     }
+/// }
+///
+/// let shape = Shape::Square(2.0);
+/// assert_eq!(shape.kind(), ShapeKind::Square);
+/// assert_eq!(shape.kind().name(), "Square");
+/// assert_eq!(ShapeKind::ALL, [ShapeKind::Circle, ShapeKind::Square]);
+/// ```
+#[macro_export]
+macro_rules! enum_kind {
+	(
+		$enum_vis: vis enum $enum_ident: ident
+		$( <[ $( $enum_gen: tt )* ]> )?
+		$( where [ $( $enum_bound: tt )* ] )?
+		{
+		    $( $var_ident: ident ( $var_ty: ty ) ),*
+		    $(,)?
+	    }
+		
+		KIND: {
+			$( #[$kind_meta: meta] )*
+			$kind_vis: vis enum $kind_ident: ident $(;)?
+		}
+	) => {
+		$crate::enum_kind! {
+			@KIND ($)
+			$enum_ident
+			$( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+			{
+				$( $var_ident ),*
+			}
+			
+			$( #[$kind_meta] )*
+			$kind_vis enum $kind_ident
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Used by the macros with an optional `KIND` section, `[]` generates nothing
+	(@SECTION [] $( $enum: tt )*) => {};
+	
+	(@SECTION [ $kind: tt ] $( $enum: tt )*) => {
+		$crate::enum_kind! {
+			$( $enum )*
+			KIND: $kind
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Kind definition, and the macro that lists its variants for `enum_variants_table!`
+	(@KIND ($d: tt)
+		$enum_ident: ident
+		$( <[ $( $enum_gen: tt )* ]> )?
+		$( where [ $( $enum_bound: tt )* ] )?
+		{
+		    $( $var_ident: ident ),*
+	    }
+		
+		$( #[$kind_meta: meta] )*
+		$kind_vis: vis enum $kind_ident: ident
+	) => {
+		$crate::paste! {
+			#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+			$( #[$kind_meta] )*
+			$kind_vis enum $kind_ident {
+				$( $var_ident ),*
+			}
+
+			impl $kind_ident {
+				pub const LENGTH: usize = $crate::count_idents!( $( $var_ident )* );
+
+				pub const ALL: [Self; Self::LENGTH] = [ $( Self::$var_ident ),* ];
+
+				pub const fn name(self) -> &'static str {
+					match self {
+						$( Self::$var_ident => stringify!($var_ident) ),*
+					}
+				}
+			}
+
+			impl $(<$( $enum_gen )*>)? $enum_ident $(<$( $enum_gen )*>)? $(where $( $enum_bound )*)? {
+				pub const fn kind(&self) -> $kind_ident {
+					match self {
+						$( Self::$var_ident(_) => $kind_ident::$var_ident ),*
+					}
+				}
+			}
+
+			impl $(<$( $enum_gen )*>)? From<&$enum_ident $(<$( $enum_gen )*>)?> for $kind_ident
+			$(where $( $enum_bound )*)?
+			{
+				fn from(value: &$enum_ident $(<$( $enum_gen )*>)?) -> Self {
+					value.kind()
+				}
+			}
+
+			#[doc(hidden)]
+			#[allow(unused_macros)]
+			macro_rules! [<__enum_kind_ $kind_ident>] {
+				( $d( $d table: tt )* ) => {
+					$crate::enum_variants_table! {
+						$kind_ident {
+							$( $var_ident ),*
+						}
+
+						$d( $d table )*
+					}
+				};
+			}
+
+			#[allow(unused_imports)]
+			pub(crate) use [<__enum_kind_ $kind_ident>] as $kind_ident;
+		}
+	};
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use std::collections::HashMap;
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			pub enum Value<['a]> {
+				Int(i32),
+				Text(&'a str),
+			}
+		}
+
+		DELEGATES: {}
+		
+		KIND: {
+			pub enum ValueKind;
+		}
+	}
+
+	crate::enum_variants_table! {
+		KIND: ValueKind;
+
+		TABLE: {
+			#[derive(Debug, PartialEq)]
+			pub struct ValueTable<T>
+		}
+
+		OPS: { Add, Default }
+	}
+
+	#[test]
+	fn test() {
+		let values = [Value::Int(1), Value::Text("a"), Value::Int(2)];
+
+		let mut counts = HashMap::new();
+		for value in &values {
+			*counts.entry(value.kind()).or_insert(0) += 1;
+		}
+
+		assert_eq!(counts[&ValueKind::Int], 2);
+		assert_eq!(ValueKind::ALL.map(ValueKind::name), ["Int", "Text"]);
+		assert!(ValueKind::Int < ValueKind::Text);
+
+		let mut table = ValueTable::default();
+		for value in &values {
+			table[value.kind()] += 1;
+		}
+
+		assert_eq!(table, ValueTable::new(2, 1));
+		assert_eq!(table + ValueTable::filled(1), ValueTable::new(3, 2));
+	}
+}
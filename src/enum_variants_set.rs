@@ -0,0 +1,468 @@
+/// Generates the sparse companions of a fieldless enum, used by the `SET` and `OPT_TABLE` sections of
+/// [enum_variants_table!](crate::enum_variants_table), and the `SET` section of [unit_enum_delegated!](crate::unit_enum_delegated).
+///
+/// # SET
+///
+/// ```pseudo
+/// SET: {
+///     [set_attributes]
+///     [set_vis] struct [set_ident];
+/// }
+/// ```
+///
+/// A bitset of variants, stored in the smallest unsigned integer with one bit per variant (at most 128).
+/// Derives `Clone, Copy, PartialEq, Eq, Hash, Default`, and `Debug` lists the names of the variants in the set.
+///
+/// - `EMPTY`, `ALL`, and the `const` constructors `from_array`, `with`, `without`
+/// - `insert`, `remove`, `contains`, `len`, `is_empty`
+/// - `iter`: the variants in the set, in declaration order
+/// - `union`, `intersection`, `difference`, also as `BitOr`, `BitAnd`, and `Not` for the complement
+/// - `to_bits`, `from_bits` (`None` on bits without a variant) and `from_bits_truncate`,
+///   the variants are numbered in declaration order
+/// - FromIterator<Enum>
+///
+/// # OPT_TABLE
+///
+/// ```pseudo
+/// OPT_TABLE: {
+///     [table_attributes]
+///     [table_vis] struct [table_ident]<[gen]>;
+/// }
+/// ```
+///
+/// A table where each variant may or may not have a value.
+///
+/// - `new`, also the `Default`
+/// - `insert`, `remove`, `get`, `get_mut`, `contains`, `len`, `is_empty`
+/// - `iter`, `iter_mut`, `into_iter`: the present entries, in declaration order
+/// - `union`: keeps the entries of `self`, adding the ones only present in `other`
+/// - `intersection`, `difference`: keeps the entries of `self` that are present/absent in `other`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! enum_variants_set {
+	//------------------------------------------------------------------------------------------------------------------
+	// Bitset of variants
+	(SET [] $( $enum: tt )*) => {};
+
+	(
+		SET [{
+			$( #[$set_meta: meta] )*
+			$set_vis: vis struct $set_ident: ident $(;)?
+		}]
+
+		$enum_ident: path {
+			$( $var_ident: ident ),*
+		}
+	) => {
+		const _: () = assert!(
+			$crate::count_idents!( $( $var_ident )* ) <= 128,
+			concat!("`", stringify!($set_ident), "` supports at most 128 variants."),
+		);
+
+		#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+		$( #[$set_meta] )*
+		$set_vis struct $set_ident {
+			bits: $crate::enum_variants_set!(@INT $( $var_ident )*),
+		}
+
+		impl $set_ident {
+			pub const EMPTY: Self = Self { bits: 0 };
+
+			pub const ALL: Self = Self {
+				bits: 0 $( | Self::bit(&<$enum_ident>::$var_ident) )*,
+			};
+
+			const fn bit(var: &$enum_ident) -> $crate::enum_variants_set!(@INT $( $var_ident )*) {
+				enum Index {
+					$( $var_ident ),*
+				}
+
+				let index = match var {
+					$( <$enum_ident>::$var_ident => Index::$var_ident as u32 ),*
+				};
+
+				1 << index
+			}
+
+			pub const fn from_array<const N: usize>(vars: [$enum_ident; N]) -> Self {
+				let mut bits = 0;
+				let mut index = 0;
+
+				while index < N {
+					bits |= Self::bit(&vars[index]);
+					index += 1;
+				}
+
+				Self { bits }
+			}
+
+			pub const fn with(self, var: $enum_ident) -> Self {
+				Self { bits: self.bits | Self::bit(&var) }
+			}
+
+			pub const fn without(self, var: $enum_ident) -> Self {
+				Self { bits: self.bits & !Self::bit(&var) }
+			}
+
+			pub const fn to_bits(self) -> $crate::enum_variants_set!(@INT $( $var_ident )*) {
+				self.bits
+			}
+
+			pub const fn from_bits(bits: $crate::enum_variants_set!(@INT $( $var_ident )*)) -> Option<Self> {
+				if bits & !Self::ALL.bits == 0 {
+					Some(Self { bits })
+				} else {
+					None
+				}
+			}
+
+			/// Drops the bits without a variant.
+			pub const fn from_bits_truncate(bits: $crate::enum_variants_set!(@INT $( $var_ident )*)) -> Self {
+				Self { bits: bits & Self::ALL.bits }
+			}
+
+			/// Returns whether the variant was not in the set.
+			pub fn insert(&mut self, var: $enum_ident) -> bool {
+				let bit = Self::bit(&var);
+				let absent = self.bits & bit == 0;
+				self.bits |= bit;
+				absent
+			}
+
+			/// Returns whether the variant was in the set.
+			pub fn remove(&mut self, var: $enum_ident) -> bool {
+				let bit = Self::bit(&var);
+				let present = self.bits & bit != 0;
+				self.bits &= !bit;
+				present
+			}
+
+			pub const fn contains(&self, var: $enum_ident) -> bool {
+				self.bits & Self::bit(&var) != 0
+			}
+
+			pub const fn len(&self) -> usize {
+				self.bits.count_ones() as usize
+			}
+
+			pub const fn is_empty(&self) -> bool {
+				self.bits == 0
+			}
+
+			pub fn iter(&self) -> impl Iterator<Item = $enum_ident> {
+				let bits = self.bits;
+
+				[ $( <$enum_ident>::$var_ident ),* ]
+					.into_iter()
+					.filter(move |var| bits & Self::bit(var) != 0)
+			}
+
+			pub const fn union(self, other: Self) -> Self {
+				Self { bits: self.bits | other.bits }
+			}
+
+			pub const fn intersection(self, other: Self) -> Self {
+				Self { bits: self.bits & other.bits }
+			}
+
+			pub const fn difference(self, other: Self) -> Self {
+				Self { bits: self.bits & !other.bits }
+			}
+		}
+
+		impl ::core::ops::BitOr for $set_ident {
+			type Output = Self;
+
+			fn bitor(self, rhs: Self) -> Self {
+				self.union(rhs)
+			}
+		}
+
+		impl ::core::ops::BitOrAssign for $set_ident {
+			fn bitor_assign(&mut self, rhs: Self) {
+				*self = self.union(rhs);
+			}
+		}
+
+		impl ::core::ops::BitAnd for $set_ident {
+			type Output = Self;
+
+			fn bitand(self, rhs: Self) -> Self {
+				self.intersection(rhs)
+			}
+		}
+
+		impl ::core::ops::BitAndAssign for $set_ident {
+			fn bitand_assign(&mut self, rhs: Self) {
+				*self = self.intersection(rhs);
+			}
+		}
+
+		impl ::core::ops::Not for $set_ident {
+			type Output = Self;
+
+			fn not(self) -> Self {
+				Self::ALL.difference(self)
+			}
+		}
+
+		impl ::core::iter::FromIterator<$enum_ident> for $set_ident {
+			fn from_iter<__I: IntoIterator<Item = $enum_ident>>(iter: __I) -> Self {
+				let mut set = Self::EMPTY;
+
+				for var in iter {
+					set.insert(var);
+				}
+
+				set
+			}
+		}
+
+		impl ::core::fmt::Debug for $set_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				let mut set = f.debug_set();
+
+				$(
+					if self.bits & Self::bit(&<$enum_ident>::$var_ident) != 0 {
+						set.entry(&format_args!(stringify!($var_ident)));
+					}
+				)*
+
+				set.finish()
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Table of optional values
+	(OPT_TABLE [] $( $enum: tt )*) => {};
+
+	(
+		OPT_TABLE [{
+			$( #[$table_meta: meta] )*
+			$table_vis: vis struct $table_ident: ident < $gen: ident > $(;)?
+		}]
+
+		$enum_ident: path {
+			$( $var_ident: ident ),*
+		}
+	) => {
+		#[allow(non_snake_case)]
+		$( #[$table_meta] )*
+		$table_vis struct $table_ident<$gen> {
+			$( $var_ident: Option<$gen> ),*
+		}
+
+		impl<$gen> $table_ident<$gen> {
+			pub const fn new() -> Self {
+				Self {
+					$( $var_ident: None ),*
+				}
+			}
+
+			/// Returns the previous value of the variant.
+			pub fn insert(&mut self, var: $enum_ident, val: $gen) -> Option<$gen> {
+				self.slot(var).replace(val)
+			}
+
+			pub fn remove(&mut self, var: $enum_ident) -> Option<$gen> {
+				self.slot(var).take()
+			}
+
+			pub fn get(&self, var: $enum_ident) -> Option<&$gen> {
+				match var {
+					$( <$enum_ident>::$var_ident => self.$var_ident.as_ref() ),*
+				}
+			}
+
+			pub fn get_mut(&mut self, var: $enum_ident) -> Option<&mut $gen> {
+				self.slot(var).as_mut()
+			}
+
+			fn slot(&mut self, var: $enum_ident) -> &mut Option<$gen> {
+				match var {
+					$( <$enum_ident>::$var_ident => &mut self.$var_ident ),*
+				}
+			}
+
+			pub fn contains(&self, var: $enum_ident) -> bool {
+				self.get(var).is_some()
+			}
+
+			pub fn len(&self) -> usize {
+				0 $( + self.$var_ident.is_some() as usize )*
+			}
+
+			pub fn is_empty(&self) -> bool {
+				self.len() == 0
+			}
+
+			#[allow(clippy::needless_lifetimes)]
+			pub fn iter<'a>(&'a self) -> impl Iterator<Item = ($enum_ident, &'a $gen)> {
+				[ $( self.$var_ident.as_ref().map(|val| (<$enum_ident>::$var_ident, val)) ),* ]
+					.into_iter()
+					.flatten()
+			}
+
+			#[allow(clippy::needless_lifetimes)]
+			pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = ($enum_ident, &'a mut $gen)> {
+				[ $( self.$var_ident.as_mut().map(|val| (<$enum_ident>::$var_ident, val)) ),* ]
+					.into_iter()
+					.flatten()
+			}
+
+			pub fn union(self, other: Self) -> Self {
+				Self {
+					$( $var_ident: self.$var_ident.or(other.$var_ident) ),*
+				}
+			}
+
+			pub fn intersection<__U>(self, other: &$table_ident<__U>) -> Self {
+				Self {
+					$( $var_ident: self.$var_ident.filter(|_| other.$var_ident.is_some()) ),*
+				}
+			}
+
+			pub fn difference<__U>(self, other: &$table_ident<__U>) -> Self {
+				Self {
+					$( $var_ident: self.$var_ident.filter(|_| other.$var_ident.is_none()) ),*
+				}
+			}
+		}
+
+		impl<$gen> ::core::default::Default for $table_ident<$gen> {
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+
+		impl<$gen> IntoIterator for $table_ident<$gen> {
+			type Item = ($enum_ident, $gen);
+			type IntoIter = ::core::iter::Flatten<
+				::core::array::IntoIter<Option<($enum_ident, $gen)>, { $crate::count_idents!( $( $var_ident )* ) }>
+			>;
+
+			fn into_iter(self) -> Self::IntoIter {
+				[ $( self.$var_ident.map(|val| (<$enum_ident>::$var_ident, val)) ),* ]
+					.into_iter()
+					.flatten()
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@INT $( $var_ident: ident )*) => {
+		<$crate::Uint<{ $crate::uint_bytes($crate::count_idents!( $( $var_ident )* )) }> as $crate::UintBits>::Int
+	};
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod tests {
+	use crate::enum_variants_table;
+
+	enum_variants_table! {
+		ENUM_OUT: {
+			#[derive(Debug, Clone, PartialEq)]
+			pub enum State {
+				Idle,
+				Walking,
+				Running,
+				Sleeping,
+			}
+		}
+
+		TABLE: {
+			pub struct StateTable<T>
+		}
+
+		SET: {
+			pub struct StateSet;
+		}
+
+		OPT_TABLE: {
+			#[derive(Debug, Clone)]
+			pub struct StateOptTable<T>;
+		}
+	}
+
+	#[test]
+	fn set() {
+		assert_eq!(size_of::<StateSet>(), 1);
+
+		let mut enabled = StateSet::EMPTY;
+		assert!(enabled.insert(State::Walking));
+		assert!(!enabled.insert(State::Walking));
+		assert!(enabled.insert(State::Sleeping));
+
+		assert!(enabled.contains(State::Sleeping));
+		assert!(!enabled.contains(State::Idle));
+		assert_eq!(enabled.len(), 2);
+		assert_eq!(enabled.iter().collect::<Vec<_>>(), [State::Walking, State::Sleeping]);
+		assert_eq!(format!("{enabled:?}"), "{Walking, Sleeping}");
+
+		let moving = [State::Walking, State::Running].into_iter().collect::<StateSet>();
+		assert_eq!(enabled.union(moving).len(), 3);
+		assert_eq!(enabled.intersection(moving).iter().collect::<Vec<_>>(), [State::Walking]);
+		assert_eq!(enabled.difference(moving).iter().collect::<Vec<_>>(), [State::Sleeping]);
+		assert_eq!(StateSet::ALL.difference(enabled).union(enabled), StateSet::ALL);
+
+		assert!(enabled.remove(State::Walking));
+		assert!(!enabled.remove(State::Walking));
+		assert!(StateSet::default().is_empty());
+	}
+
+	#[test]
+	fn flags() {
+		const MOVING: StateSet = StateSet::from_array([State::Walking, State::Running]);
+		const RESTING: StateSet = StateSet::EMPTY.with(State::Idle).with(State::Sleeping).without(State::Idle);
+
+		assert_eq!(MOVING | RESTING, StateSet::ALL.without(State::Idle));
+		assert_eq!(MOVING & RESTING, StateSet::EMPTY);
+		assert_eq!(!MOVING, StateSet::from_array([State::Idle, State::Sleeping]));
+		assert_eq!(!StateSet::EMPTY, StateSet::ALL);
+
+		let mut enabled = MOVING;
+		enabled |= RESTING;
+		enabled &= !StateSet::EMPTY.with(State::Running);
+		assert_eq!(format!("{enabled:?}"), "{Walking, Sleeping}");
+
+		assert_eq!(MOVING.to_bits(), 0b0110);
+		assert_eq!(StateSet::from_bits(0b1001), Some(StateSet::from_array([State::Idle, State::Sleeping])));
+		assert_eq!(StateSet::from_bits(0b1_0000), None);
+		assert_eq!(StateSet::from_bits_truncate(0b1_0110), MOVING);
+	}
+
+	#[test]
+	fn opt_table() {
+		let mut cache = StateOptTable::new();
+		assert_eq!(cache.insert(State::Running, "run"), None);
+		assert_eq!(cache.insert(State::Running, "sprint"), Some("run"));
+		cache.insert(State::Idle, "idle");
+
+		assert_eq!(cache.get(State::Running), Some(&"sprint"));
+		assert!(!cache.contains(State::Walking));
+		assert_eq!(cache.len(), 2);
+		assert_eq!(cache.iter().map(|(var, _)| var).collect::<Vec<_>>(), [State::Idle, State::Running]);
+
+		if let Some(val) = cache.get_mut(State::Idle) {
+			*val = "rest";
+		}
+
+		let mut other = StateOptTable::default();
+		other.insert(State::Idle, 0);
+		other.insert(State::Sleeping, 1);
+
+		assert_eq!(cache.clone().intersection(&other).into_iter().collect::<Vec<_>>(), [(State::Idle, "rest")]);
+		assert_eq!(cache.clone().difference(&other).into_iter().collect::<Vec<_>>(), [(State::Running, "sprint")]);
+
+		let mut fallback = StateOptTable::new();
+		fallback.insert(State::Idle, "wait");
+		fallback.insert(State::Walking, "walk");
+		assert_eq!(cache.clone().union(fallback).into_iter().map(|(_, val)| val).collect::<Vec<_>>(), ["rest", "walk", "sprint"]);
+
+		assert_eq!(cache.remove(State::Running), Some("sprint"));
+		assert_eq!(cache.remove(State::Running), None);
+		assert!(!cache.is_empty());
+	}
+}
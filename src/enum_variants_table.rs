@@ -18,20 +18,30 @@ macro_rules! enum_variants_table {
 	    }
 		
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident 
 			< $gen: ident > $(;)? $({})?
 		}
+		
+		$( OPS: $ops: tt )?
+		$( SET: $set: tt )?
+		$( OPT_TABLE: $opt_table: tt )?
 	) => {
 		$crate::enum_variants_table! {
 			ENUM_IN: $enum_ident;
 			
 			TABLE: {
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
 				$table_vis struct $table_ident < $gen > {
 				    $( $var_ident ),*
 			    }
 			}
+			
+			$( OPS: $ops )?
+			$( SET: $set )?
+			$( OPT_TABLE: $opt_table )?
 		}
 		
 		$( #[$enum_meta] )*
@@ -49,6 +59,7 @@ macro_rules! enum_variants_table {
 		ENUM_IN: $enum_ident: path;
 		
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident
 			< $gen: ident >
@@ -57,14 +68,50 @@ macro_rules! enum_variants_table {
 			    $(,)?
 		    }
 		}
+		
+		$( OPS: $ops: tt )?
+		$( SET: $set: tt )?
+		$( OPT_TABLE: $opt_table: tt )?
 	) => {
 		$crate::enum_variants_table! {
 			$enum_ident {
 				$( $var_ident ),*
 			}
 			
+			$( #[@ $table_serde] )?
 			$( #[$table_meta] )*
 			$table_vis struct $table_ident< $gen > ;
+			
+			$( OPS: $ops )?
+			$( SET: $set )?
+			$( OPT_TABLE: $opt_table )?
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Table + Kind of a data-carrying enum, the variants are provided by the kind, see `enum_kind!`
+	(
+		KIND: $( $kind_path: ident )::+ ;
+		
+		TABLE: {
+			$( #[@ $table_serde: ident] )?
+			$( #[$table_meta: meta] )*
+			$table_vis: vis struct $table_ident: ident
+			< $gen: ident > $(;)? $({})?
+		}
+		
+		$( OPS: $ops: tt )?
+		$( SET: $set: tt )?
+		$( OPT_TABLE: $opt_table: tt )?
+	) => {
+		$( $kind_path )::+ ! {
+			$( #[@ $table_serde] )?
+			$( #[$table_meta] )*
+			$table_vis struct $table_ident< $gen >;
+			
+			$( OPS: $ops )?
+			$( SET: $set )?
+			$( OPT_TABLE: $opt_table )?
 		}
 	};
 	
@@ -75,8 +122,52 @@ macro_rules! enum_variants_table {
 			$( $var_ident: ident ),*
 		}
 		
+		$( #[@ $table_serde: ident] )?
 		$( #[$table_meta: meta] )*
 		$table_vis: vis struct $table_ident: ident < $gen: ident > $(;)? $({})?
+		
+		$( OPS: $ops: tt )?
+		$( SET: $set: tt )?
+		$( OPT_TABLE: $opt_table: tt )?
+	) => {
+		$crate::enum_variants_table! {
+			@TABLE_INTERNAL ($)
+			$enum_ident {
+				$( $var_ident ),*
+			}
+			
+			$( #[@ $table_serde] )?
+			$( #[$table_meta] )*
+			$table_vis struct $table_ident< $gen >;
+		}
+		
+		$crate::value_table_ops! {
+			@SECTION [ $( $ops )? ]
+			$table_ident { $( $var_ident ),* }
+		}
+		
+		$crate::enum_variants_set! {
+			SET [ $( $set )? ]
+			$enum_ident { $( $var_ident ),* }
+		}
+		
+		$crate::enum_variants_set! {
+			OPT_TABLE [ $( $opt_table )? ]
+			$enum_ident { $( $var_ident ),* }
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Table definition and its helper macros
+	(
+		@TABLE_INTERNAL ($d: tt)
+		$enum_ident: path {
+			$( $var_ident: ident ),*
+		}
+		
+		$( #[@ $table_serde: ident] )?
+		$( #[$table_meta: meta] )*
+		$table_vis: vis struct $table_ident: ident < $gen: ident >;
 	) => {
 		#[allow(non_camel_case_types)]
 		#[allow(non_snake_case)]
@@ -85,31 +176,40 @@ macro_rules! enum_variants_table {
 	        $( $var_ident: $gen ),*
 	    }
 		
+		$crate::serde_impls! {
+			[ $( $table_serde )? ]
+			@TABLE
+			$table_ident
+			{ $gen }
+			{}
+			{ $( $var_ident => $var_ident: $gen ),* }
+		}
+		
 		#[allow(unused)]
 		macro_rules! table_from_const_fn {
-			( | $$var: ident | $$( -> $$ret: ty )? $$closure: block ) => {{
+			( | $d var: ident | $d( -> $d ret: ty )? $d closure: block ) => {{
 				$table_ident {
 					$( 
 						$var_ident: { 
-							let $$var = <$enum_ident>::$var_ident;
-							$$closure
+							let $d var = <$enum_ident>::$var_ident;
+							$d closure
 						} 
 					),*
 				}
 			}};
 			
-			( |_| $$( -> $$ret: ty )? $$closure: block ) => {{
+			( |_| $d( -> $d ret: ty )? $d closure: block ) => {{
 				$table_ident {
-					$( $var_ident: $$closure ),*
+					$( $var_ident: $d closure ),*
 				}
 			}};
 		}
 		
 		#[allow(unused)]
 		macro_rules! table_filled {
-			( $$with: expr ) => {{
+			( $d with: expr ) => {{
 				$table_ident {
-					$( $var_ident: $$with ),*
+					$( $var_ident: $d with ),*
 				}
 			}};
 		}
@@ -139,7 +239,7 @@ macro_rules! enum_variants_table {
 		}
 		
 		#[doc(hidden)]
-		const TABLE_LENGTH: usize = ${count($var_ident)};
+		const TABLE_LENGTH: usize = $crate::count_idents!( $( $var_ident )* );
 		
 		impl<$gen> $table_ident<$gen> {
 			pub const LENGTH: usize = TABLE_LENGTH;
@@ -165,6 +265,40 @@ macro_rules! enum_variants_table {
 			pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = ($enum_ident, &'a mut $gen)> {
 				[$( (<$enum_ident>::$var_ident, &mut self.$var_ident) ),* ].into_iter()
 			}
+			
+			pub fn map<__U>(self, mut f: impl FnMut($enum_ident, $gen) -> __U) -> $table_ident<__U> {
+				$table_ident {
+					$( $var_ident: f(<$enum_ident>::$var_ident, self.$var_ident) ),*
+				}
+			}
+			
+			/// Stops at the first error, in the order of the variants.
+			pub fn try_map<__U, __E>(
+				self,
+				mut f: impl FnMut($enum_ident, $gen) -> Result<__U, __E>,
+			) -> Result<$table_ident<__U>, __E> {
+				Ok($table_ident {
+					$( $var_ident: f(<$enum_ident>::$var_ident, self.$var_ident)? ),*
+				})
+			}
+			
+			pub fn zip<__U>(self, other: $table_ident<__U>) -> $table_ident<($gen, __U)> {
+				$table_ident {
+					$( $var_ident: (self.$var_ident, other.$var_ident) ),*
+				}
+			}
+			
+			pub fn as_ref(&self) -> $table_ident<&$gen> {
+				$table_ident {
+					$( $var_ident: &self.$var_ident ),*
+				}
+			}
+			
+			pub fn fold<__B>(self, init: __B, mut f: impl FnMut(__B, $enum_ident, $gen) -> __B) -> __B {
+				let acc = init;
+				$( let acc = f(acc, <$enum_ident>::$var_ident, self.$var_ident); )*
+				acc
+			}
 		}
 		
 		impl< $gen > IntoIterator for $table_ident< $gen > {
@@ -214,10 +348,52 @@ mod tests {
 			#[derive(Debug, Clone)] // Attributes to apply on the Table
 			pub struct DurationTable < Val >
 		}
+		
+		OPS: { Add, Sub, Mul, AddAssign, Neg, Sum, Default }
 	}
 	
 	const fn test() {
 		let table: DurationTable<i32> = table_from_const_fn!(|v| -> i32 { v as i32 });
 		let table: DurationTable<RangeInclusive<i32>> = table_filled!(5..=6);
 	}
+	
+	#[test]
+	fn test_combinators() {
+		let budget = DurationTable::from_closure(|var| var as i32 * 10);
+		let spent = DurationTable::filled(5);
+		
+		let remaining = budget.zip(spent).map(|_, (budget, spent)| budget - spent);
+		assert_eq!(remaining.as_ref().map(|var, val| *val + var as i32).into_iter().map(|(_, val)| val).collect::<Vec<_>>(), [6, 17, 28, 39]);
+		assert_eq!(remaining.clone().fold(0, |acc, _, val| acc + val), 80);
+		
+		let checked = remaining.clone().try_map(|_, val| u8::try_from(val));
+		assert_eq!(checked.unwrap()[Duration::Infinite], 35_u8);
+		
+		let failed = remaining.try_map(|var, val| if val > 20 { Err(var as i32) } else { Ok(val) });
+		assert_eq!(failed.unwrap_err(), 3);
+	}
+	
+	#[test]
+	fn test_ops() {
+		let values = |table: DurationTable<f64>| table.into_iter().map(|(_, val)| val).collect::<Vec<_>>();
+		let stats = DurationTable::new(1.0, 2.0, 3.0, 4.0);
+		let bonus = DurationTable::filled(0.5);
+		
+		assert_eq!(values(stats.clone() + bonus.clone()), [1.5, 2.5, 3.5, 4.5]);
+		assert_eq!(values(stats.clone() - bonus.clone()), [0.5, 1.5, 2.5, 3.5]);
+		assert_eq!(values(stats.clone() * bonus.clone()), [0.5, 1.0, 1.5, 2.0]);
+		assert_eq!(values(stats.clone() * 2.0), [2.0, 4.0, 6.0, 8.0]);
+		assert_eq!(values(2.0 * stats.clone()), [2.0, 4.0, 6.0, 8.0]);
+		assert_eq!(values(-stats.clone()), [-1.0, -2.0, -3.0, -4.0]);
+		assert_eq!(values(DurationTable::default()), [0.0; 4]);
+		assert_eq!(values([stats.clone(), bonus.clone(), bonus].into_iter().sum()), [2.0, 3.0, 4.0, 5.0]);
+		assert_eq!(values(std::iter::empty().sum()), [0.0; 4]);
+		
+		let mut total = stats.clone();
+		total += stats;
+		assert_eq!(values(total), [2.0, 4.0, 6.0, 8.0]);
+		
+		let mixed = DurationTable::<i64>::filled(3) * DurationTable::filled(2);
+		assert_eq!(mixed[Duration::Infinite], 6);
+	}
 }
\ No newline at end of file
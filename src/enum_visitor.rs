@@ -0,0 +1,409 @@
+/// Generates a visitor trait for a data-carrying enum, with one method per variant.
+///
+/// Opt-in through the `VISITOR` section of [delegated_enum!](crate::delegated_enum)
+/// and [extract_variants_into_enum!](crate::extract_variants_into_enum) (with `ENUM_OUT`), nothing is generated without it.
+///
+/// # Generates:
+/// - `[visitor_ident]<A = ByValue>`: the trait, with `type Output` and `visit_[var_name]` for each variant
+///   (the variant's name in snake case), which receives the variant as `<A as Access<Variant>>::Out`
+/// - `[enum_ident]::accept(self, &mut visitor)`: calls the method of the variant with the variant by value,
+///   the visitor must implement `[visitor_ident]`, or `[visitor_ident]<ByValue>`
+/// - `[enum_ident]::accept_ref(&self, &mut visitor)`: same, by shared reference, the visitor implements `[visitor_ident]<ByRef<'a>>`
+/// - `[enum_ident]::accept_mut(&mut self, &mut visitor)`: same, by mutable reference, the visitor implements `[visitor_ident]<ByMut<'a>>`
+/// - [Accept](crate::Accept) for `Enum`, `&Enum` and `&mut Enum`
+///
+/// The generics of the enum come first in the trait: `NumVisitor<'a, T, A = ByValue>`.
+///
+/// Enums with a visitor can also be matched by the type of their variants with [match_variant!](crate::match_variant).
+///
+/// # Input
+///
+/// ```pseudo
+/// [enum_vis] enum [enum_ident]<[generics]> [where [bounds]] {
+///     [var_ident]([var_type]),
+/// }
+///
+/// VISITOR: {
+///     [visitor_attributes]
+///     [visitor_vis] trait [visitor_ident];
+/// }
+/// ```
+///
+/// The enum has the same syntax as [enum_variants_convert!](crate::enum_variants_convert).
+///
+/// # Example
+///
+/// ```rust
+/// use declarative_type_state::{enum_visitor, ByRef};
+///
+/// pub struct Circle(f32);
+/// pub struct Square(f32);
+///
+/// pub enum Shape {
+///     Circle(Circle),
+///     Square(Square),
+/// }
+///
+/// enum_visitor! {
+///     pub enum Shape {
+///         Circle(Circle),
+///         Square(Square),
+///     }
+///
+///     VISITOR: {
+///         pub trait ShapeVisitor;
+///     }
+/// }
+///
+/// struct Area;
+///
+/// impl<'a> ShapeVisitor<ByRef<'a>> for Area {
+///     type Output = f32;
+///
+///     fn visit_circle(&mut self, circle: &'a Circle) -> f32 { 3.0 * circle.0 * circle.0 }
+///     fn visit_square(&mut self, square: &'a Square) -> f32 { square.0 * square.0 }
+/// }
+///
+/// assert_eq!(Shape::Square(Square(2.0)).accept_ref(&mut Area), 4.0);
+/// ```
+#[macro_export]
+macro_rules! enum_visitor {
+	(
+		$enum_vis: vis enum $enum_ident: ident
+		$( <[ $( $enum_gen: tt )* ]> )?
+		$( where [ $( $enum_bound: tt )* ] )?
+		{
+		    $( $var_ident: ident ( $var_ty: ty ) ),*
+		    $(,)?
+	    }
+
+		VISITOR: {
+			$( #[$visitor_meta: meta] )*
+			$visitor_vis: vis trait $visitor_ident: ident $(;)?
+		}
+	) => {
+		$crate::enum_visitor! {
+			@VISITOR
+			$enum_ident
+			{ $( $( $enum_gen )* , )? }
+			{ $( $( $enum_bound )* )? }
+			{
+				$( $var_ident ( $var_ty ) ),*
+			}
+
+			$( #[$visitor_meta] )*
+			$visitor_vis trait $visitor_ident
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Used by the macros with an optional `VISITOR` section, `[]` generates nothing
+	(@SECTION [] $( $enum: tt )*) => {};
+
+	(@SECTION [ $visitor: tt ] $( $enum: tt )*) => {
+		$crate::enum_visitor! {
+			$( $enum )*
+			VISITOR: $visitor
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// The generics end with a comma, so that the access mode can follow them
+	(@VISITOR
+		$enum_ident: ident
+		{ $( $gen: tt )* }
+		{ $( $bound: tt )* }
+		{
+		    $( $var_ident: ident ( $var_ty: ty ) ),*
+	    }
+
+		$( #[$visitor_meta: meta] )*
+		$visitor_vis: vis trait $visitor_ident: ident
+	) => {
+		$crate::paste! {
+			$( #[$visitor_meta] )*
+			$visitor_vis trait $visitor_ident<$( $gen )* __A = $crate::ByValue>
+			where __A: $( $crate::Access<$var_ty> + )*, $( $bound )*
+			{
+				type Output;
+
+				$(
+					fn [<visit_ $var_ident:snake>](&mut self, var: <__A as $crate::Access<$var_ty>>::Out) -> Self::Output;
+				)*
+			}
+
+			impl<$( $gen )*> $enum_ident<$( $gen )*> where $( $bound )* {
+				pub fn accept<V>(self, visitor: &mut V) -> V::Output
+				where V: $visitor_ident<$( $gen )* $crate::ByValue>
+				{
+					match self {
+						$( Self::$var_ident(var) => visitor.[<visit_ $var_ident:snake>](var) ),*
+					}
+				}
+
+				pub fn accept_ref<'__a, V>(&'__a self, visitor: &mut V) -> V::Output
+				where V: $visitor_ident<$( $gen )* $crate::ByRef<'__a>>
+				{
+					match self {
+						$( Self::$var_ident(var) => visitor.[<visit_ $var_ident:snake>](var) ),*
+					}
+				}
+
+				pub fn accept_mut<'__a, V>(&'__a mut self, visitor: &mut V) -> V::Output
+				where V: $visitor_ident<$( $gen )* $crate::ByMut<'__a>>
+				{
+					match self {
+						$( Self::$var_ident(var) => visitor.[<visit_ $var_ident:snake>](var) ),*
+					}
+				}
+			}
+
+			impl<$( $gen )* V> $crate::Accept<V> for $enum_ident<$( $gen )*>
+			where V: $visitor_ident<$( $gen )* $crate::ByValue>, $( $bound )*
+			{
+				type Output = V::Output;
+
+				fn accept_visitor(self, visitor: &mut V) -> V::Output { self.accept(visitor) }
+			}
+
+			impl<'__a, $( $gen )* V> $crate::Accept<V> for &'__a $enum_ident<$( $gen )*>
+			where V: $visitor_ident<$( $gen )* $crate::ByRef<'__a>>, $( $bound )*
+			{
+				type Output = V::Output;
+
+				fn accept_visitor(self, visitor: &mut V) -> V::Output { self.accept_ref(visitor) }
+			}
+
+			impl<'__a, $( $gen )* V> $crate::Accept<V> for &'__a mut $enum_ident<$( $gen )*>
+			where V: $visitor_ident<$( $gen )* $crate::ByMut<'__a>>, $( $bound )*
+			{
+				type Output = V::Output;
+
+				fn accept_visitor(self, visitor: &mut V) -> V::Output { self.accept_mut(visitor) }
+			}
+
+			// Visitor of `match_variant!`, only implemented when there are as many closures as variants
+			impl<$( $gen )* __A, __R, __H, $( [<__Index $var_ident>] ),*>
+			$visitor_ident<$( $gen )* __A>
+			for $crate::Arms<__R, __H, ( $( [<__Index $var_ident>], )* ), { $crate::count_idents!( $( $var_ident )* ) }>
+			where
+				__A: $( $crate::Access<$var_ty> + )*,
+				__H: $( $crate::Handle<<__A as $crate::Access<$var_ty>>::Out, __R, [<__Index $var_ident>]> + )*,
+				$( $bound )*
+			{
+				type Output = __R;
+
+				$(
+					fn [<visit_ $var_ident:snake>](&mut self, var: <__A as $crate::Access<$var_ty>>::Out) -> __R {
+						$crate::Handle::handle(&mut self.0, var)
+					}
+				)*
+			}
+		}
+	};
+}
+
+/// Matches an enum with a visitor by the type of its variants, one closure per variant.
+///
+/// The enum can be passed by value, by shared or by mutable reference,
+/// the closures then take the variants the same way.
+/// Every variant must be handled exactly once, a missing, repeated or unknown type fails to compile.
+///
+/// # Input
+///
+/// ```pseudo
+/// match_variant!([value], {
+///     [var_type] => |[binding]| [expr],
+/// })
+/// ```
+///
+/// - `[value]`: `Enum`, `&Enum` or `&mut Enum`, the enum must have a `VISITOR`, see [enum_visitor!](crate::enum_visitor).
+/// - `[var_type]`: Type of the variant as received by the closure. (e.g., `&Circle` when matching `&Shape`)
+///
+/// The arms may be listed in any order, they are picked by type, not by position.
+///
+/// # Example
+///
+/// ```rust
+/// use declarative_type_state::{delegated_enum, match_variant};
+///
+/// pub struct Circle(f32);
+/// pub struct Square(f32);
+///
+/// delegated_enum! {
+///     ENUM_OUT: {
+///         pub enum Shape {
+///             Circle(Circle),
+///             Square(Square),
+///         }
+///     }
+///
+///     DELEGATES: {}
+///
+///     VISITOR: {
+///         pub trait ShapeVisitor;
+///     }
+/// }
+///
+/// let scale = 2.0;
+/// let mut shape = Shape::Circle(Circle(1.0));
+///
+/// match_variant!(&mut shape, {
+///     &mut Square => |square| square.0 *= scale,
+///     &mut Circle => |circle| circle.0 *= scale,
+/// });
+///
+/// let size = match_variant!(shape, {
+///     Circle => |Circle(radius)| radius * 2.0,
+///     Square => |Square(side)| side,
+/// });
+///
+/// assert_eq!(size, 4.0);
+/// ```
+#[macro_export]
+macro_rules! match_variant {
+	(
+		$value: expr, {
+			$( $var_ty: ty => | $binding: pat_param | $body: expr ),*
+			$(,)?
+		}
+	) => {
+		$crate::Accept::accept_visitor(
+			$value,
+			&mut $crate::Arms::<_, _, _, { [ $( stringify!($var_ty) ),* ].len() }>::new(
+				$crate::match_variant!(@LIST $( |$binding: $var_ty| $body ),*)
+			),
+		)
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Closures as a list: `(F1, (F2, ()))`
+	(@LIST) => { () };
+
+	(@LIST $head: expr $( , $tail: expr )*) => {
+		($head, $crate::match_variant!(@LIST $( $tail ),*))
+	};
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use crate::{ByMut, ByRef, ByValue};
+
+	#[derive(Debug, PartialEq)]
+	pub struct Walking(u32);
+
+	#[derive(Debug, PartialEq)]
+	pub struct Frozen;
+
+	crate::delegated_enum! {
+		ENUM_OUT: {
+			#[derive(Debug, PartialEq)]
+			pub enum Mover {
+				Walking(Walking),
+				Frozen(Frozen),
+			}
+		}
+
+		DELEGATES: {}
+
+		VISITOR: {
+			pub trait MoverVisitor;
+		}
+	}
+
+	struct Speed;
+
+	impl<'a> MoverVisitor<ByRef<'a>> for Speed {
+		type Output = u32;
+
+		fn visit_walking(&mut self, walking: &'a Walking) -> u32 { walking.0 }
+
+		fn visit_frozen(&mut self, _frozen: &'a Frozen) -> u32 { 0 }
+	}
+
+	struct Boost(u32);
+
+	impl<'a> MoverVisitor<ByMut<'a>> for Boost {
+		type Output = ();
+
+		fn visit_walking(&mut self, walking: &'a mut Walking) { walking.0 += self.0 }
+
+		fn visit_frozen(&mut self, _frozen: &'a mut Frozen) {}
+	}
+
+	struct Thaw;
+
+	impl MoverVisitor for Thaw {
+		type Output = Walking;
+
+		fn visit_walking(&mut self, walking: Walking) -> Walking { walking }
+
+		fn visit_frozen(&mut self, _frozen: Frozen) -> Walking { Walking(0) }
+	}
+
+	#[test]
+	fn visitor() {
+		let mut mover = Mover::Walking(Walking(2));
+		mover.accept_mut(&mut Boost(3));
+		assert_eq!(mover.accept_ref(&mut Speed), 5);
+		assert_eq!(Mover::Frozen(Frozen).accept_ref(&mut Speed), 0);
+
+		assert_eq!(mover.accept(&mut Thaw), Walking(5));
+		assert_eq!(Mover::Frozen(Frozen).accept(&mut Thaw), Walking(0));
+	}
+
+	#[test]
+	fn match_variant() {
+		let mut mover = Mover::Walking(Walking(1));
+		let mut frozen = 0;
+
+		match_variant!(&mut mover, {
+			&mut Frozen => |_| frozen += 1,
+			&mut Walking => |walking| walking.0 *= 4,
+		});
+
+		let speed = match_variant!(&mover, {
+			&Walking => |Walking(speed)| *speed,
+			&Frozen => |_| 0,
+		});
+
+		assert_eq!((speed, frozen), (4, 0));
+
+		let name = match_variant!(Mover::Frozen(Frozen), {
+			Walking => |_| "walking",
+			Frozen => |Frozen| "frozen",
+		});
+
+		assert_eq!(name, "frozen");
+	}
+
+	crate::extract_variants_into_enum! {
+		ENUM_OUT: {
+			#[vars(derive(Debug, PartialEq))]
+			pub enum Shape<['a, T]> where [T: Copy + 'a] {
+				Circle <['a, T]> { radius: &'a T },
+				Square <[T]> (T),
+			}
+		}
+
+		DELEGATES: {}
+
+		VISITOR: {
+			pub trait ShapeVisitor;
+		}
+	}
+
+	#[test]
+	fn generics() {
+		let radius = 2;
+		let shape = Shape::<i32>::Circle(Circle { radius: &radius });
+
+		let size = match_variant!(&shape, {
+			&Square<i32> => |Square(side)| *side,
+			&Circle<i32> => |circle| *circle.radius * 2,
+		});
+
+		assert_eq!(size, 4);
+	}
+}
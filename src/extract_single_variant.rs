@@ -4,6 +4,7 @@ macro_rules! extract_single_variant {
 	//------------------------------------------------------------------------------------------------------------------
 	// Tuple
     (
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		$( #[$var_meta: meta] )*
 		$var_vis: vis 
@@ -21,11 +22,21 @@ macro_rules! extract_single_variant {
 		<$( $generic )*>
 		( $( pub $field_ty, )* )
 		where $( $bound )* ;
+		
+		$crate::serde_impls! {
+			$serde
+			@TUPLE
+			$var_ident
+			{ $( $generic )* }
+			{ $( $bound )* }
+			( $( $field_ty ),* )
+		}
 	};
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// Struct
 	(
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		$( #[$var_meta: meta] )*
 		$var_vis: vis 
@@ -45,11 +56,21 @@ macro_rules! extract_single_variant {
 		{
 			$( pub $field_name: $field_ty, )*
 		}
+		
+		$crate::serde_impls! {
+			$serde
+			@STRUCT
+			$var_ident
+			{ $( $generic )* }
+			{ $( $bound )* }
+			{ $( $field_name: $field_ty ),* }
+		}
 	};
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// Unit
 	(
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		$( #[$var_meta: meta] )*
 		$var_vis: vis 
@@ -60,11 +81,14 @@ macro_rules! extract_single_variant {
 		$( #[$all_meta] )*
 		$( #[$var_meta] )*
 		$var_vis struct $var_ident;
+		
+		$crate::serde_impls! { $serde @UNIT $var_ident }
 	};
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// Ignored Tuple
     (
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		[@SKIP]
 		$( #[$var_meta: meta] )*
@@ -82,6 +106,7 @@ macro_rules! extract_single_variant {
 	//------------------------------------------------------------------------------------------------------------------
 	// Ignored Struct
 	(
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		[@SKIP]
 		$( #[$var_meta: meta] )*
@@ -99,6 +124,7 @@ macro_rules! extract_single_variant {
 	//------------------------------------------------------------------------------------------------------------------
 	// Ignored Unit
 	(
+		$serde: tt
 		{ $( #[$all_meta: meta] )* }
 		[@SKIP]
 		$( #[$var_meta: meta] )*
@@ -128,6 +128,7 @@
 #[macro_export]
 macro_rules! extract_variants {
     (
+	    $( #[@ $serde: ident] )?
 	    #[vars( $( $all_meta: meta ),* $(,)? )]
 		$( #[$enum_meta: meta] )*
 		$enum_vis: vis enum $enum_ident: ident
@@ -148,6 +149,7 @@ macro_rules! extract_variants {
     ) => {
 	    $crate::extract_variants! {
 		    @TOKENIZE 
+		    [ $( $serde )? ]
 		    { $( #[$all_meta] )* }
 		    $( #[$enum_meta] )*
 		    $enum_vis enum $enum_ident {
@@ -165,6 +167,7 @@ macro_rules! extract_variants {
     };
 	
 	(@TOKENIZE
+		$serde: tt
 		$all_meta_tt: tt
 		$( #[$enum_meta: meta] )*
 		$enum_vis: vis enum $enum_ident: ident {
@@ -181,6 +184,7 @@ macro_rules! extract_variants {
 	) => {
 		$(
 			$crate::extract_single_variant! {
+				$serde
 				$all_meta_tt
 				$( [@ $ignore] )?
 				$( #[$var_meta] )*
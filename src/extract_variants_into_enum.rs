@@ -8,6 +8,10 @@
 /// - Attributes on individual variants are optional
 /// - Since this macro generates an enum with the same name as the input enum, 
 ///   the input enum should be merely a template, it should not be defined outside this macro
+/// - With `ENUM_OUT`, the optional `KIND` section generates a fieldless kind of the enum, see [enum_kind!](crate::enum_kind)
+/// - With `ENUM_OUT`, the optional `VISITOR` section generates a visitor trait for the enum, see [enum_visitor!](crate::enum_visitor)
+/// - With the feature `serde`, placing `#[@SERDE]` before `#[vars]` implements `Serialize`/`Deserialize`
+///   for the enum and its variants. Without the marker nothing is implemented, so the variants can derive them instead
 /// 
 /// # Example:
 /// 
@@ -69,6 +73,7 @@ macro_rules! extract_variants_into_enum {
 	//------------------------------------------------------------------------------------------------------------------
 	// Generated Enum
 	(
+		$( #[@ $serde: ident] )?
 		#[vars( $( $all_meta: meta ),* $(,)? )]
 		$( #[$enum_meta: meta] )*
 		$enum_vis: vis enum $enum_ident: ident
@@ -100,6 +105,7 @@ macro_rules! extract_variants_into_enum {
 	    }
 		
 		$crate::extract_variants! {
+			$( #[@ $serde] )?
 			#[vars( $( $all_meta ),* )]
 		    $( #[$enum_meta] )*
 		    $enum_vis enum $enum_ident
@@ -126,12 +132,22 @@ macro_rules! extract_variants_into_enum {
 			    $( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
 		    }
 	    }
+		
+		$crate::serde_impls! {
+			[ $( $serde )? ]
+			@ENUM
+			$enum_ident
+			{ $( $( $enum_gen )* )? }
+			{ $( $( $enum_bound )* )? }
+			{ $( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),* }
+		}
     };
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// Generated Enum + Delegates
 	(
 		ENUM_OUT: {
+			$( #[@ $serde: ident] )?
 			#[vars( $( $all_meta: meta ),* $(,)? )]
 			$( #[$enum_meta: meta] )*
 			$enum_vis: vis enum $enum_ident: ident
@@ -151,24 +167,14 @@ macro_rules! extract_variants_into_enum {
 		    }
 		}
 		
-		DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-		    
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+		DELEGATES: $delegates: tt
+		
+		$( KIND: $kind: tt )?
+		
+		$( VISITOR: $visitor: tt )?
     ) => {
 		$crate::extract_variants_into_enum! {
+			$( #[@ $serde] )?
 			#[vars( $( $all_meta ),* )]
 			$( #[$enum_meta] )*
 			$enum_vis enum $enum_ident
@@ -187,6 +193,26 @@ macro_rules! extract_variants_into_enum {
 		    }
 		}
 		
+		$crate::enum_kind! {
+			@SECTION [ $( $kind )? ]
+		    $enum_vis enum $enum_ident
+		    $( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+		    {
+			    $( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
+		    }
+	    }
+		
+		$crate::enum_visitor! {
+			@SECTION [ $( $visitor )? ]
+		    $enum_vis enum $enum_ident
+		    $( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+		    {
+			    $( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
+		    }
+	    }
+		
 		$crate::enum_delegate_impls! {
 			ENUM_IN: {
 				$enum_ident
@@ -197,22 +223,7 @@ macro_rules! extract_variants_into_enum {
 			    }
 			}
 			
-			DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )? 
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-			    
-			    $(
-				    impl { 
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+			DELEGATES: $delegates
 		}
     };
 }
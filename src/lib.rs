@@ -1,32 +1,57 @@
-#![feature(try_trait_v2)]
-#![feature(type_changing_struct_update)]
-#![feature(macro_metavar_expr)]
+#![cfg_attr(feature = "nightly", feature(try_trait_v2, try_trait_v2_residual))]
 #![allow(clippy::tabs_in_doc_comments)]
 
+// Lets the attribute macros refer to `::declarative_type_state` from within this crate's tests.
+extern crate self as declarative_type_state;
+
+pub use replace_with::{replace_with_or_abort, replace_with_or_else};
+pub use transition_history::{TransitionHistory, TransitionRecord};
 pub use transition_result::{
 	Transition,
 	Transition::{ChangedTo, Unchanged},
 };
+pub use visitor::{Accept, Access, ByMut, ByRef, ByValue};
+#[doc(hidden)]
+pub use visitor::{Arms, Handle, Here, There};
 
+// Arms that define nested `macro_rules!` take a `($d: tt)` argument, invoked with `($)`,
+// so that `$d` is a literal `$` for the metavariables of the nested macro.
 mod extract_variants_into_enum;
 mod newtype_table;
 mod type_state_enum;
 mod type_table;
 mod type_value_table;
 
+mod delegatable_trait;
 mod delegated_enum;
 mod extract_single_variant;
 mod extract_variants;
 
 mod enum_delegate_impls;
+mod enum_delegate_ops;
+mod enum_kind;
 mod enum_variants_convert;
+mod enum_variants_set;
 mod enum_variants_table;
+mod enum_visitor;
 
 mod unit_enum_delegate_impls;
 mod unit_enum_delegated;
 mod unit_enum_variants_convert;
 
+mod replace_with;
+mod transition_history;
 mod transition_result;
+mod visitor;
+
+mod bits;
+mod count_idents;
+
+mod serde_impls;
+mod value_table_ops;
+
+#[cfg(any(feature = "macros", test))]
+pub mod attr;
 
 pub trait FromEnum<Enum>: Sized {
 	fn from_enum(t: Enum) -> Option<Self>;
@@ -56,9 +81,49 @@ where for<'a> &'a mut Variant: FromEnum<&'a mut Enum>
 	fn from_enum_mut(t: &mut Enum) -> Option<&mut Self> { <&mut Variant>::from_enum(t) }
 }
 
+#[diagnostic::on_unimplemented(
+	message = "transition from `{Self}` to `{Next}` is not declared",
+	label = "`{Self}` cannot transition to `{Next}`",
+	note = "add `{Self} => [{Next}]` to the `TRANSITIONS` section of `type_state_enum!`"
+)]
+pub trait CanTransitionTo<Next> {}
+
+/// Called by `transition_to` on the state struct it is leaving, see the `HOOKS` section of `type_state_enum!`.
+///
+/// `to` is the name of the state being entered.
+pub trait OnExit {
+	fn on_exit(&mut self, _to: &'static str) {}
+}
+
+/// Called by `transition_to` on the state struct it has just entered, see the `HOOKS` section of `type_state_enum!`.
+///
+/// `from` is the name of the state that was left.
+pub trait OnEnter {
+	fn on_enter(&mut self, _from: &'static str) {}
+}
+
+/// Name of a state type, as written in the `ENUM_OUT` section of `type_state_enum!`.
+pub trait StateName {
+	const NAME: &'static str;
+}
+
+#[doc(hidden)]
+pub use bits::{uint_bytes, Uint, UintBits};
 #[doc(hidden)]
 pub use paste::paste;
 
+/// `Unpin` only if `T` is, see the typed receivers of `enum_delegate_impls!`.
+/// The lifetime keeps the bound from being trivial when `T` has no generics.
+#[doc(hidden)]
+pub struct PinGuard<'a, T: ?Sized>(core::marker::PhantomData<&'a ()>, T);
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_impls::IdentSeed;
+
 pub trait MemberOf<Table> {
 	type MemberType;
 	fn get_in_table(table: &Table) -> &Self::MemberType;
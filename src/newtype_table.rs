@@ -6,10 +6,16 @@ macro_rules! newtype_table {
 		ENUM: {
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
-					$var_ident: ident ($var_ty: ty)
+					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
+					($var_ty: ty)
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
@@ -20,31 +26,22 @@ macro_rules! newtype_table {
 			$table_vis: vis struct $table_ident: ident $(;)? $({})?
 		}
 
-		DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )?
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+		DELEGATES: $delegates: tt
 	) => {
 		$crate::newtype_table! {
 			ENUM: {
 			    #[vars( $( $all_meta ),* )]
 			    $( #[$enum_meta] )*
-			    $enum_vis enum $enum_ident {
+			    $enum_vis enum $enum_ident
+			    $( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+			    {
 					$(
 						$( #[$var_meta] )*
-						$var_ident($var_ty)
+						$var_ident
+						$( <[ $( $var_gen )* ]> )?
+						($var_ty)
+						$( where [ $( $var_bound )* ] )?
 				    ),*
 			    }
 		    }
@@ -57,27 +54,15 @@ macro_rules! newtype_table {
 
 		$crate::enum_delegate_impls! {
 			ENUM_IN: {
-				$enum_ident {
+				$enum_ident
+				$( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+				{
 					$( $var_ident ( $var_ty ) ),*
 			    }
 		    }
 
-			DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )?
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-
-			    $(
-				    impl {
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+			DELEGATES: $delegates
 		}
 	};
 
@@ -87,10 +72,16 @@ macro_rules! newtype_table {
 		ENUM: {
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
-					$var_ident: ident ($var_ty: ty)
+					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
+					($var_ty: ty)
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
@@ -104,16 +95,25 @@ macro_rules! newtype_table {
 		$crate::extract_variants! {
 			#[vars( $( $all_meta ),* )]
 			$( #[$enum_meta] )*
-			$enum_vis enum $enum_ident {
+			$enum_vis enum $enum_ident
+			$( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+			{
 			    $(
 			        $( #[$var_meta] )*
-			        $var_ident($var_ty)
+			        $var_ident
+			        $( <[ $( $var_gen )* ]> )?
+			        ($var_ty)
+			        $( where [ $( $var_bound )* ] )?
 			    ),*
 		    }
 		}
 
 		$( #[$enum_meta] )*
-		$enum_vis enum $enum_ident {
+		$enum_vis enum $enum_ident
+		$( <$( $enum_gen )*> )?
+		$( where $( $enum_bound )* )?
+		{
 		    $(
 		        $( #[$var_meta] )*
 		        $var_ident($var_ty)
@@ -125,8 +125,11 @@ macro_rules! newtype_table {
 			$enum_ident
 
 			$( #[$table_meta] )*
-			$table_vis struct $table_ident {
-			    $($var_ident: $var_ty),*
+			$table_vis struct $table_ident
+			[ $( $( $enum_gen )* )? ]
+			[ $( $( $enum_bound )* )? ]
+			{
+			    $( $var_ident [ $( $( $var_gen )* )? ]: $var_ty ),*
 			}
 		}
 	};
@@ -202,3 +205,37 @@ mod tests_2 {
 		}
 	}
 }
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_generics {
+	use crate::newtype_table;
+
+	newtype_table! {
+		ENUM: {
+			#[vars(derive(Debug, Clone))]
+			#[derive(Debug, Clone, PartialEq)]
+			pub enum Resource<[T]> where [T: Copy] {
+				Gold<[T]>(T),
+				Wood<[T]>(T),
+				Workers(u32),
+			}
+		}
+
+		TABLE: {
+			#[derive(Debug, Clone)]
+			pub struct ResourceTable;
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut table = ResourceTable::new(2.5, 4.0, 3);
+		*table.get_mut::<Wood<_>>() += 1.0;
+
+		assert_eq!(table.get::<Gold<f32>>(), &2.5);
+		assert_eq!(table.get::<Wood<f32>>(), &5.0);
+		assert_eq!(table.get::<Workers>(), &3);
+		assert!(matches!(table.iter().next(), Some(ResourceRef::Gold(&2.5))));
+	}
+}
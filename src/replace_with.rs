@@ -0,0 +1,86 @@
+use std::{mem, ptr};
+
+/// Moves the value out of `dest`, then writes back the value returned by `f`.
+///
+/// `dest` has no valid value while `f` runs, if `f` panics the process is aborted.
+/// Use [replace_with_or_else] to recover from panics instead.
+pub fn replace_with_or_abort<T, R>(dest: &mut T, f: impl FnOnce(T) -> (T, R)) -> R {
+	struct AbortOnDrop;
+
+	impl Drop for AbortOnDrop {
+		fn drop(&mut self) { std::process::abort(); }
+	}
+
+	let guard = AbortOnDrop;
+
+	// SAFETY: the value is read once and `dest` is written before returning,
+	// if `f` panics the guard aborts before `dest` can be observed or dropped.
+	let result = unsafe {
+		let (next, result) = f(ptr::read(dest));
+		ptr::write(dest, next);
+		result
+	};
+
+	mem::forget(guard);
+	result
+}
+
+/// Moves the value out of `dest`, then writes back the value returned by `f`.
+///
+/// If `f` panics, `dest` is filled with the value returned by `on_panic` before the panic resumes.
+pub fn replace_with_or_else<T, R>(dest: &mut T, on_panic: impl FnOnce() -> T, f: impl FnOnce(T) -> (T, R)) -> R {
+	struct Restore<'a, T, F: FnOnce() -> T> {
+		dest:     &'a mut T,
+		on_panic: Option<F>,
+	}
+
+	impl<T, F: FnOnce() -> T> Drop for Restore<'_, T, F> {
+		fn drop(&mut self) {
+			if let Some(on_panic) = self.on_panic.take() {
+				// SAFETY: only reached while unwinding out of `f`, when `dest` holds no value.
+				unsafe { ptr::write(self.dest, on_panic()) };
+			}
+		}
+	}
+
+	let mut guard = Restore { dest, on_panic: Some(on_panic) };
+
+	// SAFETY: the value is read once and `dest` is written before returning,
+	// if `f` panics the guard writes the fallback value.
+	unsafe {
+		let (next, result) = f(ptr::read(guard.dest));
+		guard.on_panic = None;
+		ptr::write(guard.dest, next);
+		result
+	}
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use std::panic::{catch_unwind, AssertUnwindSafe};
+
+	use super::*;
+
+	#[test]
+	fn replace() {
+		let mut text = String::from("a");
+		let len = replace_with_or_abort(&mut text, |text| (text + "b", 2));
+		assert_eq!((text.as_str(), len), ("ab", 2));
+
+		let mut text = String::from("a");
+		replace_with_or_else(&mut text, String::new, |text| (text + "c", ()));
+		assert_eq!(text, "ac");
+	}
+
+	#[test]
+	fn panic() {
+		let mut text = String::from("a");
+		let result = catch_unwind(AssertUnwindSafe(|| {
+			replace_with_or_else(&mut text, || String::from("fallback"), |_| -> (String, ()) { panic!() })
+		}));
+
+		assert!(result.is_err());
+		assert_eq!(text, "fallback");
+	}
+}
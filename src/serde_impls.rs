@@ -0,0 +1,806 @@
+#[cfg(feature = "serde")]
+use serde::de::{DeserializeSeed, Deserializer, Error, Unexpected, Visitor};
+
+/// Deserializes the name (or index) of a variant, field or table key, into its index in `names`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct IdentSeed {
+	names: &'static [&'static str],
+	kind:  IdentKind,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, PartialEq)]
+enum IdentKind {
+	/// Unknown names are an error
+	Variant,
+	/// Unknown names are an error
+	Key,
+	/// Unknown names are ignored, like the structs generated by `derive(Deserialize)`
+	Field,
+}
+
+#[cfg(feature = "serde")]
+impl IdentSeed {
+	pub const fn variant(names: &'static [&'static str]) -> Self {
+		Self {
+			names,
+			kind: IdentKind::Variant,
+		}
+	}
+
+	pub const fn key(names: &'static [&'static str]) -> Self {
+		Self {
+			names,
+			kind: IdentKind::Key,
+		}
+	}
+
+	pub const fn field(names: &'static [&'static str]) -> Self {
+		Self {
+			names,
+			kind: IdentKind::Field,
+		}
+	}
+
+	fn unknown<E: Error>(self, name: &str) -> Result<Option<usize>, E> {
+		match self.kind {
+			IdentKind::Variant => Err(E::unknown_variant(name, self.names)),
+			IdentKind::Key => Err(E::unknown_field(name, self.names)),
+			IdentKind::Field => Ok(None),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> DeserializeSeed<'de> for IdentSeed {
+	type Value = Option<usize>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		deserializer.deserialize_identifier(self)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for IdentSeed {
+	type Value = Option<usize>;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(formatter, "one of {:?}", self.names)
+	}
+
+	fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+		match usize::try_from(v) {
+			Ok(index) if index < self.names.len() => Ok(Some(index)),
+			_ if self.kind == IdentKind::Field => Ok(None),
+			_ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+		}
+	}
+
+	fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+		match self.names.iter().position(|name| *name == v) {
+			Some(index) => Ok(Some(index)),
+			None => self.unknown(v),
+		}
+	}
+
+	fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		match std::str::from_utf8(v) {
+			Ok(v) => self.visit_str(v),
+			Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+		}
+	}
+}
+
+/// Implements `Serialize`/`Deserialize` for the types generated by the other macros, 
+/// when the feature `serde` is enabled and the item is marked with `#[@SERDE]`.
+///
+/// The first argument is the marker: `[]` (no impls) or `[SERDE]`.
+///
+/// Bounds on the field types are higher-ranked (`for<'__a> Field: Serialize`), so that the impls are only checked where used:
+/// types with fields that aren't serializable still compile.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! serde_impls {
+	//------------------------------------------------------------------------------------------------------------------
+	// Opt-in marker, the impls would conflict with `derive(Serialize, Deserialize)` on the same types
+	([] $( $input: tt )*) => {};
+	
+	([SERDE] $( $input: tt )*) => {
+		$crate::serde_impls! { $( $input )* }
+	};
+	
+	([$marker: ident] $( $input: tt )*) => {
+		compile_error!(concat!("Unknown marker `#[@", stringify!($marker), "]`, expected `#[@SERDE]`."));
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Struct with named fields, serialized as a struct
+	(@STRUCT
+		$ident: ident
+		{ $( $generic: tt )* }
+		$bounds_tt: tt
+		$fields_tt: tt
+	) => {
+		$crate::serde_impls! {
+			@GENERIC_NAMES
+			{ @STRUCT_IMPL $ident { $( $generic )* } $bounds_tt $fields_tt }
+			[]
+			$( $generic )*
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Table, serialized as a map of `key => field`
+	(@TABLE
+		$ident: ident
+		{ $( $generic: tt )* }
+		$bounds_tt: tt
+		$fields_tt: tt
+	) => {
+		$crate::serde_impls! {
+			@GENERIC_NAMES
+			{ @TABLE_IMPL $ident { $( $generic )* } $bounds_tt $fields_tt }
+			[]
+			$( $generic )*
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Enum of newtype variants, externally tagged: `{ "Variant": value }`
+	(@ENUM
+		$ident: ident
+		{ $( $generic: tt )* }
+		$bounds_tt: tt
+		$vars_tt: tt
+	) => {
+		$crate::serde_impls! {
+			@GENERIC_NAMES
+			{ @ENUM_IMPL $ident { $( $generic )* } $bounds_tt $vars_tt }
+			[]
+			$( $generic )*
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Strips the bounds from the generic parameters, appending the names to the callback: `'a, T: Clone` => `{ 'a, T, }`
+	(@GENERIC_NAMES { $( $callback: tt )* } [ $( $name: tt )* ]) => {
+		$crate::serde_impls! {
+			$( $callback )*
+			{ $( $name )* }
+		}
+	};
+
+	(@GENERIC_NAMES $callback: tt [ $( $name: tt )* ] $lifetime: lifetime $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback [ $( $name )* $lifetime, ] [] $( $rest )* }
+	};
+
+	(@GENERIC_NAMES $callback: tt [ $( $name: tt )* ] $generic: ident $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback [ $( $name )* $generic, ] [] $( $rest )* }
+	};
+
+	(@GENERIC_BOUNDS $callback: tt $names: tt []) => {
+		$crate::serde_impls! { @GENERIC_NAMES $callback $names }
+	};
+
+	(@GENERIC_BOUNDS $callback: tt $names: tt [] , $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_NAMES $callback $names $( $rest )* }
+	};
+
+	// `[ $depth ]` holds one token per unclosed `<`
+	(@GENERIC_BOUNDS $callback: tt $names: tt [ $( $depth: tt )* ] < $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback $names [ < $( $depth )* ] $( $rest )* }
+	};
+
+	(@GENERIC_BOUNDS $callback: tt $names: tt [ $open: tt $( $depth: tt )* ] > $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback $names [ $( $depth )* ] $( $rest )* }
+	};
+
+	(@GENERIC_BOUNDS $callback: tt $names: tt [ $open: tt $open_2: tt $( $depth: tt )* ] >> $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback $names [ $( $depth )* ] $( $rest )* }
+	};
+
+	(@GENERIC_BOUNDS $callback: tt $names: tt $depth: tt $next: tt $( $rest: tt )*) => {
+		$crate::serde_impls! { @GENERIC_BOUNDS $callback $names $depth $( $rest )* }
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@STRUCT_IMPL
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		{ $( $field: ident: $field_ty: ty ),* $(,)? }
+		{ $( $arg: tt )* }
+	) => {
+		impl<$( $generic )*> $crate::serde::Serialize for $ident<$( $arg )*>
+		where
+			$( for<'__a> $field_ty: $crate::serde::Serialize, )*
+			$( $bound )*
+		{
+			fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+			where __S: $crate::serde::Serializer
+			{
+				use $crate::serde::ser::SerializeStruct;
+
+				let mut state = serializer.serialize_struct(
+					stringify!($ident),
+					$crate::count_idents!( $( $field )* ),
+				)?;
+				$( state.serialize_field(stringify!($field), &self.$field)?; )*
+				state.end()
+			}
+		}
+
+		$crate::serde_impls! {
+			@DESERIALIZE_FIELDS
+			$ident
+			{ $( $generic )* }
+			{ $( $arg )* }
+			{ $( $bound )* }
+			{ $( $field => $field: $field_ty ),* }
+			IdentSeed::field
+			deserialize_struct(stringify!($ident), FIELDS)
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@TABLE_IMPL
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		{ $( $key: ident => $field: ident: $field_ty: ty ),* $(,)? }
+		{ $( $arg: tt )* }
+	) => {
+		impl<$( $generic )*> $crate::serde::Serialize for $ident<$( $arg )*>
+		where
+			$( for<'__a> $field_ty: $crate::serde::Serialize, )*
+			$( $bound )*
+		{
+			fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+			where __S: $crate::serde::Serializer
+			{
+				use $crate::serde::ser::SerializeMap;
+
+				let mut state = serializer.serialize_map(Some($crate::count_idents!( $( $key )* )))?;
+				$( state.serialize_entry(stringify!($key), &self.$field)?; )*
+				state.end()
+			}
+		}
+
+		$crate::serde_impls! {
+			@DESERIALIZE_FIELDS
+			$ident
+			{ $( $generic )* }
+			{ $( $arg )* }
+			{ $( $bound )* }
+			{ $( $key => $field: $field_ty ),* }
+			IdentSeed::key
+			deserialize_map()
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@DESERIALIZE_FIELDS
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $arg: tt )* }
+		{ $( $bound: tt )* }
+		{ $( $key: ident => $field: ident: $field_ty: ty ),* }
+		IdentSeed::$seed: ident
+		$deserialize_fn: ident ( $( $deserialize_arg: expr ),* )
+	) => {
+		impl<'de, $( $generic )*> $crate::serde::Deserialize<'de> for $ident<$( $arg )*>
+		where
+			$( for<'__a> $field_ty: $crate::serde::Deserialize<'de>, )*
+			$( $bound )*
+		{
+			fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+			where __D: $crate::serde::Deserializer<'de>
+			{
+				use $crate::serde::de::{Error, IgnoredAny, MapAccess, SeqAccess};
+
+				const FIELDS: &[&str] = &[ $( stringify!($key) ),* ];
+
+				struct __Visitor<__T>(std::marker::PhantomData<__T>);
+
+				#[allow(non_snake_case)]
+				impl<'de, $( $generic )*> $crate::serde::de::Visitor<'de> for __Visitor<$ident<$( $arg )*>>
+				where
+					$( for<'__a> $field_ty: $crate::serde::Deserialize<'de>, )*
+					$( $bound )*
+				{
+					type Value = $ident<$( $arg )*>;
+
+					fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+						formatter.write_str(concat!("struct ", stringify!($ident)))
+					}
+
+					#[allow(unused_mut, unused_assignments)]
+					fn visit_seq<__A>(self, mut seq: __A) -> Result<Self::Value, __A::Error>
+					where __A: SeqAccess<'de>
+					{
+						let mut len = 0;
+						$(
+							let Some($field) = seq.next_element::<$field_ty>()?
+							else { return Err(Error::invalid_length(len, &self)) };
+							len += 1;
+						)*
+
+						Ok($ident { $( $field ),* })
+					}
+
+					fn visit_map<__A>(self, mut map: __A) -> Result<Self::Value, __A::Error>
+					where __A: MapAccess<'de>
+					{
+						$( let mut $field: Option<$field_ty> = None; )*
+
+						while let Some(index) = map.next_key_seed($crate::IdentSeed::$seed(FIELDS))? {
+							let Some(name) = index.map(|index| FIELDS[index])
+							else {
+								map.next_value::<IgnoredAny>()?;
+								continue;
+							};
+
+							$(
+								if name == stringify!($key) {
+									if $field.is_some() {
+										return Err(Error::duplicate_field(stringify!($key)));
+									}
+
+									$field = Some(map.next_value()?);
+								}
+							)*
+						}
+
+						$(
+							let Some($field) = $field
+							else { return Err(Error::missing_field(stringify!($key))) };
+						)*
+
+						Ok($ident { $( $field ),* })
+					}
+				}
+
+				deserializer.$deserialize_fn( $( $deserialize_arg, )* __Visitor::<Self>(std::marker::PhantomData) )
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Tuple struct, fields are named after the `[ __f0 .. ]` pool
+	(@TUPLE
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		( $( $field_ty: ty ),* $(,)? )
+	) => {
+		$crate::serde_impls! {
+			@TUPLE_NAMES
+			$ident
+			{ $( $generic )* }
+			{ $( $bound )* }
+			[]
+			[ __f0 __f1 __f2 __f3 __f4 __f5 __f6 __f7 __f8 __f9 __f10 __f11 __f12 __f13 __f14 __f15 ]
+			$( $field_ty, )*
+		}
+	};
+
+	(@TUPLE_NAMES
+		$ident: ident
+		$generics_tt: tt
+		$bounds_tt: tt
+		[ $( $named: tt )* ]
+		[ $next_name: ident $( $name: ident )* ]
+		$field_ty: ty,
+		$( $rest: tt )*
+	) => {
+		$crate::serde_impls! {
+			@TUPLE_NAMES
+			$ident
+			$generics_tt
+			$bounds_tt
+			[ $( $named )* ($next_name: $field_ty) ]
+			[ $( $name )* ]
+			$( $rest )*
+		}
+	};
+
+	(@TUPLE_NAMES
+		$ident: ident
+		$generics_tt: tt
+		$bounds_tt: tt
+		$named_tt: tt
+		[]
+		$field_ty: ty,
+		$( $rest: tt )*
+	) => {
+		compile_error!(concat!(
+			"`#[@SERDE]` supports tuple variants with up to 16 fields, `", stringify!($ident), "` has more.\n\
+			 Help: use named fields, or derive `Serialize`/`Deserialize` with `#[vars(..)]` instead."
+		));
+	};
+	
+	(@TUPLE_NAMES
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		[ $( $named: tt )* ]
+		$pool: tt
+	) => {
+		$crate::serde_impls! {
+			@GENERIC_NAMES
+			{ @TUPLE_IMPL $ident { $( $generic )* } { $( $bound )* } [ $( $named )* ] }
+			[]
+			$( $generic )*
+		}
+	};
+
+	(@TUPLE_IMPL
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		[ $( ($field: ident: $field_ty: ty) )* ]
+		{ $( $arg: tt )* }
+	) => {
+		impl<$( $generic )*> $crate::serde::Serialize for $ident<$( $arg )*>
+		where
+			$( for<'__a> $field_ty: $crate::serde::Serialize, )*
+			$( $bound )*
+		{
+			fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+			where __S: $crate::serde::Serializer
+			{
+				use $crate::serde::ser::SerializeTupleStruct;
+
+				let $ident( $( $field ),* ) = self;
+				let mut state = serializer.serialize_tuple_struct(
+					stringify!($ident),
+					$crate::count_idents!( $( $field )* ),
+				)?;
+				$( state.serialize_field($field)?; )*
+				state.end()
+			}
+		}
+
+		impl<'de, $( $generic )*> $crate::serde::Deserialize<'de> for $ident<$( $arg )*>
+		where
+			$( for<'__a> $field_ty: $crate::serde::Deserialize<'de>, )*
+			$( $bound )*
+		{
+			fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+			where __D: $crate::serde::Deserializer<'de>
+			{
+				use $crate::serde::de::{Error, SeqAccess};
+
+				struct __Visitor<__T>(std::marker::PhantomData<__T>);
+
+				impl<'de, $( $generic )*> $crate::serde::de::Visitor<'de> for __Visitor<$ident<$( $arg )*>>
+				where
+					$( for<'__a> $field_ty: $crate::serde::Deserialize<'de>, )*
+					$( $bound )*
+				{
+					type Value = $ident<$( $arg )*>;
+
+					fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+						formatter.write_str(concat!("tuple struct ", stringify!($ident)))
+					}
+
+					#[allow(unused_mut, unused_assignments)]
+					fn visit_seq<__A>(self, mut seq: __A) -> Result<Self::Value, __A::Error>
+					where __A: SeqAccess<'de>
+					{
+						let mut len = 0;
+						$(
+							let Some($field) = seq.next_element::<$field_ty>()?
+							else { return Err(Error::invalid_length(len, &self)) };
+							len += 1;
+						)*
+
+						Ok($ident( $( $field ),* ))
+					}
+				}
+
+				deserializer.deserialize_tuple_struct(
+					stringify!($ident),
+					$crate::count_idents!( $( $field )* ),
+					__Visitor::<Self>(std::marker::PhantomData),
+				)
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Unit struct
+	(@UNIT $ident: ident) => {
+		impl $crate::serde::Serialize for $ident {
+			fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+			where __S: $crate::serde::Serializer
+			{
+				serializer.serialize_unit_struct(stringify!($ident))
+			}
+		}
+
+		impl<'de> $crate::serde::Deserialize<'de> for $ident {
+			fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+			where __D: $crate::serde::Deserializer<'de>
+			{
+				struct __Visitor;
+
+				impl<'de> $crate::serde::de::Visitor<'de> for __Visitor {
+					type Value = $ident;
+
+					fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+						formatter.write_str(concat!("unit struct ", stringify!($ident)))
+					}
+
+					fn visit_unit<__E>(self) -> Result<Self::Value, __E>
+					where __E: $crate::serde::de::Error
+					{
+						Ok($ident)
+					}
+				}
+
+				deserializer.deserialize_unit_struct(stringify!($ident), __Visitor)
+			}
+		}
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@ENUM_IMPL
+		$ident: ident
+		{ $( $generic: tt )* }
+		{ $( $bound: tt )* }
+		{ $( $var_ident: ident ( $var_ty: ty ) ),* $(,)? }
+		{ $( $arg: tt )* }
+	) => {
+		impl<$( $generic )*> $crate::serde::Serialize for $ident<$( $arg )*>
+		where
+			$( for<'__a> $var_ty: $crate::serde::Serialize, )*
+			$( $bound )*
+		{
+			fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+			where __S: $crate::serde::Serializer
+			{
+				#[allow(non_camel_case_types)]
+				#[repr(u32)]
+				enum Index { $( $var_ident ),* }
+
+				match self {
+					$(
+						Self::$var_ident(var) => {
+							serializer.serialize_newtype_variant(
+								stringify!($ident),
+								Index::$var_ident as u32,
+								stringify!($var_ident),
+								var,
+							)
+						}
+					)*
+				}
+			}
+		}
+
+		impl<'de, $( $generic )*> $crate::serde::Deserialize<'de> for $ident<$( $arg )*>
+		where
+			$( for<'__a> $var_ty: $crate::serde::Deserialize<'de>, )*
+			$( $bound )*
+		{
+			fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+			where __D: $crate::serde::Deserializer<'de>
+			{
+				use $crate::serde::de::{EnumAccess, VariantAccess};
+
+				const VARIANTS: &[&str] = &[ $( stringify!($var_ident) ),* ];
+
+				#[allow(non_camel_case_types)]
+				enum Index { $( $var_ident ),* }
+
+				struct __Visitor<__T>(std::marker::PhantomData<__T>);
+
+				impl<'de, $( $generic )*> $crate::serde::de::Visitor<'de> for __Visitor<$ident<$( $arg )*>>
+				where
+					$( for<'__a> $var_ty: $crate::serde::Deserialize<'de>, )*
+					$( $bound )*
+				{
+					type Value = $ident<$( $arg )*>;
+
+					fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+						formatter.write_str(concat!("enum ", stringify!($ident)))
+					}
+
+					fn visit_enum<__A>(self, data: __A) -> Result<Self::Value, __A::Error>
+					where __A: EnumAccess<'de>
+					{
+						let (index, variant) = data.variant_seed($crate::IdentSeed::variant(VARIANTS))?;
+
+						match index {
+							$(
+								Some(index) if index == Index::$var_ident as usize => {
+									variant.newtype_variant::<$var_ty>().map($ident::$var_ident)
+								}
+							)*
+							_ => unreachable!("`IdentSeed::variant` rejects unknown variants"),
+						}
+					}
+				}
+
+				deserializer.deserialize_enum(stringify!($ident), VARIANTS, __Visitor::<Self>(std::marker::PhantomData))
+			}
+		}
+	};
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! serde_impls {
+	([] $( $input: tt )*) => {};
+	
+	([SERDE] $( $input: tt )*) => {
+		compile_error!("`#[@SERDE]` requires the feature `serde` of `declarative_type_state`.");
+	};
+	
+	([$marker: ident] $( $input: tt )*) => {
+		compile_error!(concat!("Unknown marker `#[@", stringify!($marker), "]`, expected `#[@SERDE]`."));
+	};
+}
+
+#[allow(unused)]
+#[cfg(all(test, feature = "serde"))]
+mod test {
+	use serde_json::json;
+
+	use crate::{enum_variants_table, extract_variants_into_enum, type_state_enum, type_table};
+
+	type_state_enum! {
+		STATE: {
+			#[@SERDE]
+			#[derive(Debug, PartialEq)]
+			pub struct Villager {
+				hunger: f32,
+			}
+		}
+
+		ENUM_OUT: {
+			#[@SERDE]
+			#[vars(derive(Debug, PartialEq))]
+			#[derive(Debug, PartialEq)]
+			pub enum VillagerEnum {
+				Idle,
+				Hungry { since: f32 },
+				Fleeing(u8),
+			}
+		}
+
+		DELEGATES: {}
+	}
+
+	extract_variants_into_enum! {
+		#[@SERDE]
+		#[vars(derive(Debug, PartialEq))]
+		#[derive(Debug, PartialEq)]
+		pub enum Value<['a, T]> where [T: Clone] {
+			Int { int: i32 },
+			Pair <[T]> (i32, T) where [T: Clone],
+			Ref <['a]> (&'a str),
+			Unit,
+		}
+	}
+
+	type_table! {
+		ENUM_OUT: {
+			#[@SERDE]
+			#[vars(derive(Debug, PartialEq))]
+			pub enum Duration {
+				Seconds(f64),
+				Infinite,
+			}
+		}
+
+		TABLE: {
+			#[@SERDE]
+			#[derive(Debug, PartialEq)]
+			pub struct DurationTable;
+		}
+	}
+
+	enum_variants_table! {
+		ENUM_OUT: {
+			#[derive(Debug, Clone, Copy)]
+			pub enum Axis {
+				X,
+				Y,
+			}
+		}
+
+		TABLE: {
+			#[@SERDE]
+			#[derive(Debug, PartialEq)]
+			pub struct AxisTable<T>
+		}
+	}
+
+	// Without `#[@SERDE]`, the impls can be derived instead
+	extract_variants_into_enum! {
+		#[vars(derive(Debug, PartialEq, serde::Serialize, serde::Deserialize))]
+		#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+		pub enum Message {
+			Ping,
+			Pong { id: u32 },
+		}
+	}
+
+	#[test]
+	fn state_enum() {
+		let villager = VillagerEnum::Hungry(Villager {
+			hunger: 0.5,
+			state:  Hungry { since: 2.0 },
+		});
+
+		let value = serde_json::to_value(&villager).unwrap();
+		assert_eq!(value, json!({ "Hungry": { "hunger": 0.5, "state": { "since": 2.0 } } }));
+		assert_eq!(serde_json::from_value::<VillagerEnum>(value).unwrap(), villager);
+
+		let villager = VillagerEnum::Fleeing(Villager { hunger: 0.0, state: Fleeing(3) });
+		let value = serde_json::to_value(&villager).unwrap();
+		assert_eq!(value, json!({ "Fleeing": { "hunger": 0.0, "state": [3] } }));
+		assert_eq!(serde_json::from_value::<VillagerEnum>(value).unwrap(), villager);
+	}
+
+	#[test]
+	fn state_enum_unknown_names() {
+		let idle = json!({ "Idle": { "state": null, "hunger": 1.0, "unknown": 5 } });
+		assert_eq!(
+			serde_json::from_value::<VillagerEnum>(idle).unwrap(),
+			VillagerEnum::Idle(Villager { hunger: 1.0, state: Idle })
+		);
+
+		assert!(serde_json::from_value::<VillagerEnum>(json!({ "Sleeping": { "hunger": 1.0, "state": null } })).is_err());
+		assert!(serde_json::from_value::<VillagerEnum>(json!({ "Idle": { "state": null } })).is_err());
+	}
+
+	#[test]
+	fn generic_enum() {
+		let pair = Value::<u8>::Pair(Pair(1, 2));
+		let json = serde_json::to_string(&pair).unwrap();
+		assert_eq!(json, r#"{"Pair":[1,2]}"#);
+		assert_eq!(serde_json::from_str::<Value<u8>>(&json).unwrap(), pair);
+
+		let unit = Value::<u8>::Unit(Unit);
+		let json = serde_json::to_string(&unit).unwrap();
+		assert_eq!(json, r#"{"Unit":null}"#);
+		assert_eq!(serde_json::from_str::<Value<u8>>(&json).unwrap(), unit);
+
+		let borrowed = Value::<u8>::Ref(Ref("text"));
+		let json = serde_json::to_string(&borrowed).unwrap();
+		assert_eq!(json, r#"{"Ref":["text"]}"#);
+	}
+
+	#[test]
+	fn tables() {
+		let table = DurationTable::new(Seconds(1.5), Infinite);
+		let value = serde_json::to_value(&table).unwrap();
+		assert_eq!(value, json!({ "Seconds": [1.5], "Infinite": null }));
+		assert_eq!(serde_json::from_value::<DurationTable>(value).unwrap(), table);
+
+		let table = AxisTable::new(1, 2);
+		let value = serde_json::to_value(&table).unwrap();
+		assert_eq!(value, json!({ "X": 1, "Y": 2 }));
+		assert_eq!(serde_json::from_value::<AxisTable<i32>>(value).unwrap(), table);
+	}
+
+	#[test]
+	fn table_keys() {
+		assert!(serde_json::from_value::<AxisTable<i32>>(json!({ "X": 1 })).is_err());
+		assert!(serde_json::from_value::<AxisTable<i32>>(json!({ "X": 1, "Y": 2, "Z": 3 })).is_err());
+		assert!(serde_json::from_str::<AxisTable<i32>>(r#"{ "X": 1, "Y": 2, "X": 3 }"#).is_err());
+	}
+
+	#[test]
+	fn derived() {
+		let pong = Message::Pong(Pong { id: 3 });
+		assert_eq!(serde_json::to_value(&pong).unwrap(), json!({ "Pong": { "id": 3 } }));
+	}
+}
@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+/// One state change recorded by a tracked type-state enum, see the `TRACKED` section of `type_state_enum!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransitionRecord {
+	pub from: &'static str,
+	pub to:   &'static str,
+	pub tick: u64,
+}
+
+/// Bounded ring buffer of [TransitionRecord]s, once full the oldest record is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionHistory {
+	records:  VecDeque<TransitionRecord>,
+	capacity: usize,
+}
+
+impl TransitionHistory {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			records: VecDeque::with_capacity(capacity),
+			capacity,
+		}
+	}
+
+	pub fn push(&mut self, record: TransitionRecord) {
+		if self.capacity == 0 {
+			return;
+		}
+
+		if self.records.len() == self.capacity {
+			self.records.pop_front();
+		}
+
+		self.records.push_back(record);
+	}
+
+	/// Iterates from the oldest to the most recent record.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = &TransitionRecord> + ExactSizeIterator {
+		self.records.iter()
+	}
+
+	pub fn last(&self) -> Option<&TransitionRecord> { self.records.back() }
+
+	pub fn len(&self) -> usize { self.records.len() }
+
+	pub fn is_empty(&self) -> bool { self.records.is_empty() }
+
+	pub fn capacity(&self) -> usize { self.capacity }
+
+	pub fn clear(&mut self) { self.records.clear(); }
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn record(tick: u64) -> TransitionRecord {
+		TransitionRecord { from: "A", to: "B", tick }
+	}
+
+	#[test]
+	fn bounded() {
+		let mut history = TransitionHistory::new(2);
+		history.push(record(1));
+		history.push(record(2));
+		history.push(record(3));
+
+		assert_eq!(history.len(), 2);
+		assert_eq!(history.iter().map(|r| r.tick).collect::<Vec<_>>(), [2, 3]);
+		assert_eq!(history.last(), Some(&record(3)));
+
+		history.clear();
+		assert!(history.is_empty());
+
+		let mut disabled = TransitionHistory::new(0);
+		disabled.push(record(1));
+		assert!(disabled.is_empty());
+	}
+}
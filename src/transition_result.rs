@@ -1,20 +1,154 @@
+#[cfg(feature = "nightly")]
+use std::{
+	convert::Infallible,
+	ops::{ControlFlow, FromResidual, Residual, Try},
+};
 use Transition::{Unchanged, ChangedTo};
-use std::ops::{ControlFlow, FromResidual, Try};
 
 pub enum Transition<TCurr, TNext> {
 	Unchanged(TCurr),
 	ChangedTo(TNext),
 }
 
-impl<TCurr, TNext> FromResidual for Transition<TCurr, TNext> {
-	fn from_residual(residual: <Self as Try>::Residual) -> Self {
-		ChangedTo(residual)
+impl<TCurr, TNext> Transition<TCurr, TNext> {
+	pub const fn is_changed(&self) -> bool {
+		matches!(self, ChangedTo(_))
+	}
+
+	pub const fn is_unchanged(&self) -> bool {
+		matches!(self, Unchanged(_))
+	}
+
+	/// Maps the value inside `Unchanged`, leaving `ChangedTo` untouched.
+	pub fn map_unchanged<T>(self, f: impl FnOnce(TCurr) -> T) -> Transition<T, TNext> {
+		match self {
+			Unchanged(same) => Unchanged(f(same)),
+			ChangedTo(next) => ChangedTo(next),
+		}
+	}
+
+	/// Maps the value inside `ChangedTo`, leaving `Unchanged` untouched.
+	pub fn map_changed<T>(self, f: impl FnOnce(TNext) -> T) -> Transition<TCurr, T> {
+		match self {
+			Unchanged(same) => Unchanged(same),
+			ChangedTo(next) => ChangedTo(f(next)),
+		}
+	}
+
+	/// Runs the next check if the state did not change, short-circuits on `ChangedTo`.
+	///
+	/// Equivalent to using `?` on `self`, then calling `f` with the output.
+	pub fn and_then<T>(self, f: impl FnOnce(TCurr) -> Transition<T, TNext>) -> Transition<T, TNext> {
+		match self {
+			Unchanged(same) => f(same),
+			ChangedTo(next) => ChangedTo(next),
+		}
+	}
+
+	/// Gives the new state a chance to be rejected or redirected, `Unchanged` is kept as is.
+	pub fn or_else<T>(self, f: impl FnOnce(TNext) -> Transition<TCurr, T>) -> Transition<TCurr, T> {
+		match self {
+			Unchanged(same) => Unchanged(same),
+			ChangedTo(next) => f(next),
+		}
+	}
+
+	/// # Panics
+	///
+	/// If `self` is `Unchanged`.
+	#[track_caller]
+	pub fn unwrap_changed(self) -> TNext {
+		match self {
+			Unchanged(_) => panic!("called `Transition::unwrap_changed()` on an `Unchanged` value"),
+			ChangedTo(next) => next,
+		}
+	}
+
+	/// `Unchanged` becomes `Ok`, `ChangedTo` becomes `Err`, matching the `?` semantics of [Transition].
+	pub fn into_result(self) -> Result<TCurr, TNext> {
+		match self {
+			Unchanged(same) => Ok(same),
+			ChangedTo(next) => Err(next),
+		}
+	}
+
+	/// Collapses both branches into `TNext`, usually the state enum.
+	pub fn into_enum(self) -> TNext
+		where TCurr: Into<TNext>
+	{
+		match self {
+			Unchanged(same) => same.into(),
+			ChangedTo(next) => next,
+		}
 	}
 }
 
+impl<TCurr, TNext> From<Result<TCurr, TNext>> for Transition<TCurr, TNext> {
+	fn from(result: Result<TCurr, TNext>) -> Self {
+		match result {
+			Ok(same) => Unchanged(same),
+			Err(next) => ChangedTo(next),
+		}
+	}
+}
+
+impl<TCurr, TNext> From<Transition<TCurr, TNext>> for Result<TCurr, TNext> {
+	fn from(transition: Transition<TCurr, TNext>) -> Self {
+		transition.into_result()
+	}
+}
+
+/// Stable alternative to using `?` on a [Transition](crate::Transition).
+///
+/// Evaluates to the value inside `Unchanged`, 
+/// or returns early from the enclosing function with the value inside `ChangedTo`.
+///
+/// # Example
+///
+/// ```rust
+/// use declarative_type_state::{bail_if_changed, Transition, Unchanged, ChangedTo};
+///
+/// fn check_hunger(hunger: f64) -> Transition<f64, &'static str> {
+///     if hunger > 0.8 { ChangedTo("Hungry") } else { Unchanged(hunger) }
+/// }
+///
+/// fn tick(hunger: f64) -> Transition<f64, &'static str> {
+///     let hunger = bail_if_changed!(check_hunger(hunger + 0.1));
+///     Unchanged(hunger)
+/// }
+///
+/// assert!(matches!(tick(0.5), Unchanged(_)));
+/// assert!(matches!(tick(0.75), ChangedTo("Hungry")));
+/// ```
+#[macro_export]
+macro_rules! bail_if_changed {
+	($transition: expr $(,)?) => {
+		match $transition {
+			$crate::Unchanged(same) => same,
+			$crate::ChangedTo(next) => return $crate::ChangedTo(next),
+		}
+	};
+}
+
+#[cfg(feature = "nightly")]
+impl<TCurr, TNext> FromResidual<Transition<Infallible, TNext>> for Transition<TCurr, TNext> {
+	fn from_residual(residual: Transition<Infallible, TNext>) -> Self {
+		match residual {
+			Unchanged(never) => match never {},
+			ChangedTo(next) => ChangedTo(next),
+		}
+	}
+}
+
+#[cfg(feature = "nightly")]
+impl<TCurr, TNext> Residual<TCurr> for Transition<Infallible, TNext> {
+	type TryType = Transition<TCurr, TNext>;
+}
+
+#[cfg(feature = "nightly")]
 impl<TCurr, TNext> Try for Transition<TCurr, TNext> {
 	type Output = TCurr;
-	type Residual = TNext;
+	type Residual = Transition<Infallible, TNext>;
 
 	fn from_output(output: Self::Output) -> Self {
 		Unchanged(output)
@@ -26,12 +160,65 @@ impl<TCurr, TNext> Try for Transition<TCurr, TNext> {
 				ControlFlow::Continue(same)
 			}
 			ChangedTo(new) => {
-				ControlFlow::Break(new)
+				ControlFlow::Break(ChangedTo(new))
 			}
 		}
 	}
 }
 
+#[allow(unused)]
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn check(value: i32) -> Transition<i32, &'static str> {
+		if value > 10 { ChangedTo("Big") } else { Unchanged(value) }
+	}
+
+	#[test]
+	fn combinators() {
+		assert!(check(5).is_unchanged());
+		assert!(check(15).is_changed());
+
+		assert!(matches!(check(5).map_unchanged(|v| v * 2), Unchanged(10)));
+		assert!(matches!(check(15).map_changed(str::len), ChangedTo(3)));
+
+		assert!(matches!(check(5).and_then(|v| check(v * 3)), ChangedTo("Big")));
+		assert!(matches!(check(2).and_then(|v| check(v * 3)), Unchanged(6)));
+		assert!(matches!(check(15).and_then(|v| check(v - 10)), ChangedTo("Big")));
+
+		assert!(matches!(check(15).or_else(|_| Unchanged::<i32, ()>(0)), Unchanged(0)));
+		assert!(matches!(check(5).or_else(|_| ChangedTo::<i32, ()>(())), Unchanged(5)));
+
+		assert_eq!(check(15).unwrap_changed(), "Big");
+		assert_eq!(check(5).into_result(), Ok(5));
+		assert_eq!(Result::from(check(15)), Err("Big"));
+		assert!(matches!(Transition::from(Ok::<i32, &str>(1)), Unchanged(1)));
+
+		let as_string: Transition<&str, String> = Unchanged("same");
+		assert_eq!(as_string.into_enum(), "same");
+	}
+
+	#[test]
+	#[should_panic]
+	fn unwrap_changed() {
+		check(5).unwrap_changed();
+	}
+
+	#[cfg(feature = "nightly")]
+	#[test]
+	fn try_operator() {
+		fn twice(value: i32) -> Transition<i32, &'static str> {
+			let value = check(value)?;
+			check(value * 2)
+		}
+
+		assert!(matches!(twice(2), Unchanged(4)));
+		assert!(matches!(twice(7), ChangedTo("Big")));
+		assert!(matches!(twice(11), ChangedTo("Big")));
+	}
+}
+
 /*
 // My crate defines this struct
 pub enum Transition<TCurr, TNext> {
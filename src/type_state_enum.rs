@@ -1,14 +1,205 @@
+/// Generates an enum where each variant wraps the state struct with a different state type,
+/// along with the methods to transition between states.
+///
+/// ## Also implements:
+/// - `Deref`/`DerefMut` from the enum to `State<dyn Any>`
+/// - `transition_to` and `with_state` on `State<Curr>`
+/// - `transition_in_place` on the enum, which transitions through `&mut self`
+/// - From<State<Variant>> for Enum
+/// - TryFrom<Enum> for State<Variant>
+/// - [StateName](crate::StateName) on each state type
+///
+/// # Input
+/// - `STATE`: The state struct, either user provided or generated.
+/// - `ENUM_OUT`: Defines the output enum, its variants are extracted into their own types.
+/// - `DELEGATES`: Specifies the traits and methods to be implemented for the enum, 
+///   same syntax as [delegated_enum!](crate::delegated_enum).
+///   Traits implemented by the state types alone are delegated to the state field: `impl trait Tick via .state { .. }`.
+/// - `HOOKS`: Optional, makes `transition_to` call [OnExit](crate::OnExit) and [OnEnter](crate::OnEnter).
+/// - `TRANSITIONS`: Optional, declares which states each state is allowed to transition to.
+/// - `TRACKED`: Optional, generates a wrapper around the enum that records its state changes.
+/// - `KIND`: Optional, generates a fieldless kind of the enum, see [enum_kind!](crate::enum_kind).
+///
+/// ## STATE - User provided:
+///
+/// ```pseudo
+/// STATE: [struct_ident] <[generics]> where [bounds] { [state_field], [other_fields] }
+/// ```
+///
+/// - `[struct_ident]`: Identifier of the state struct, generic over the state type. (e.g., `Villager`)
+/// - `[generics]`: Optional, the other generic parameters of the struct, names only, 
+///   must be placed inside brackets. (e.g., `<['w, W]>`)
+///   The state type must be the last parameter of the struct: `Villager<'w, W, S: ?Sized>`.
+/// - `[bounds]`: Optional where clause of the struct, must be placed inside brackets. (e.g., `where [W: World]`)
+/// - `[state_field]`: Name of the field that holds the state. (e.g., `state`)
+/// - `[other_fields]`: All the other fields of the struct, `with_state` moves them into the new struct.
+///
+/// The generics are propagated into the enum (`VillagerEnum<'w, W>`) and everything implemented for it.
+///
+/// ## STATE - Generated:
+///
+/// ```pseudo
+/// STATE: {
+///     [struct_attributes]
+///     [struct_vis] struct [struct_ident] <[generics]> where [bounds] {
+///         [field_ident]: [field_type],
+///     }
+/// }
+/// ```
+///
+/// Generates the struct with the field `state: __State` added last, `__State` being the state type.
+///
+/// ## Serde:
+///
+/// With the feature `serde`, `#[@SERDE]` implements `Serialize`/`Deserialize` for the item it is placed on:
+/// as the first attribute of the generated `STATE` struct, or before `#[vars]` in `ENUM_OUT` (enum and variants).
+/// Unmarked items get no impls, they are free to derive them.
+///
+/// ## HOOKS:
+///
+/// ```pseudo
+/// HOOKS: {
+///     OnEnter: [ [var_A], [var_B] ],
+///     OnExit: [ [var_C] ],
+/// }
+/// ```
+///
+/// `OnEnter` and `OnExit` are both optional, in either order.
+///
+/// `transition_to` calls `on_exit(to)` on the old struct before moving its fields, 
+/// then `on_enter(from)` on the new struct, `to` and `from` being the [StateName](crate::StateName)s of the other state.
+/// The listed states must implement the hook for their struct (e.g., `impl OnEnter for Villager<Hungry>`),
+/// the others get the default no-op implementation. Use `HOOKS: {}` to enable the hooks without overriding any.
+///
+/// ## TRANSITIONS:
+///
+/// ```pseudo
+/// TRANSITIONS: {
+///     [from_var_A] => [ [to_var_B], [to_var_C] ],
+///     [from_var_B] => [ [to_var_A] ],
+/// }
+/// ```
+///
+/// When present, `transition_to` only compiles for the declared edges.
+/// When absent, any state may transition to any other.
+///
+/// The enum also gets `graph_dot()` and `graph_mermaid()`, which render the states 
+/// (and the declared transitions, if any) as Graphviz DOT and Mermaid diagrams.
+///
+/// ## TRACKED:
+///
+/// ```pseudo
+/// TRACKED: {
+///     [tracked_attributes]
+///     [tracked_vis] struct [tracked_ident];
+/// }
+/// ```
+///
+/// The wrapper owns the enum and records each `ChangedTo` as a [TransitionRecord](crate::TransitionRecord)
+/// (`from` variant, `to` variant, `tick` provided by the caller) in a bounded [TransitionHistory](crate::TransitionHistory).
+/// State changes go through `replace_with(tick, |state| ...)`, built on the enum's `transition_in_place`,
+/// or the unchecked `force_set(tick, next)`. `history()` and `clear_history()` query and reset the log.
+///
+/// # Example
+///
+/// ```rust
+/// use declarative_type_state::{type_state_enum, Transition};
+///
+/// pub struct Villager<T: ?Sized> {
+///     state: T,
+/// }
+///
+/// type_state_enum! {
+///     STATE: Villager { state }
+///
+///     ENUM_OUT: {
+///         #[vars()]
+///         pub enum VillagerEnum {
+///             Idle,
+///             Hungry,
+///             Fleeing,
+///         }
+///     }
+///
+///     DELEGATES: {}
+///
+///     TRANSITIONS: {
+///         Idle => [Hungry, Fleeing],
+///         Hungry => [Idle],
+///     }
+/// }
+///
+/// let idle = Villager { state: Idle };
+/// let hungry: Transition<_, VillagerEnum> = idle.transition_to(Hungry);
+/// ```
+///
+/// Transitioning through an undeclared edge does not compile:
+///
+/// ```compile_fail
+/// # use declarative_type_state::{type_state_enum, Transition};
+/// # pub struct Villager<T: ?Sized> { state: T }
+/// # type_state_enum! {
+/// #     STATE: Villager { state }
+/// #     ENUM_OUT: { #[vars()] pub enum VillagerEnum { Idle, Hungry, Fleeing } }
+/// #     DELEGATES: {}
+/// #     TRANSITIONS: { Idle => [Hungry, Fleeing], Hungry => [Idle] }
+/// # }
+/// let hungry = Villager { state: Hungry };
+/// let fleeing: Transition<_, VillagerEnum> = hungry.transition_to(Fleeing);
+/// ```
 #[macro_export]
 macro_rules! type_state_enum {
 	//------------------------------------------------------------------------------------------------------------------
 	// User provided state struct
     (
 	    STATE: $state_ident: ident
-	    $( <[$( $state_gen: tt )*]> )?
+	    $( <[ $( $state_gen: tt ),* $(,)? ]> )?
 		$( where [$( $state_gen_bound: tt )*] )?
-	    { $state_field_ident: ident }
+	    { $state_field_ident: ident $(, $other_field_ident: ident )* $(,)? }
+
+	    ENUM_OUT: $enum_out: tt
+	    
+	    DELEGATES: $delegates: tt
+	    
+	    $( HOOKS: $hooks: tt )?
+	    
+	    $( TRANSITIONS: $transitions: tt )?
+	    
+	    $( TRACKED: $tracked: tt )?
+	    
+	    $( KIND: $kind: tt )?
+    ) => {
+	    $crate::type_state_enum! {
+		    @STATE_ENUM
+		    $state_ident
+		    { $( $( $state_gen, )* )? }
+		    { $( $( $state_gen_bound )* )? }
+		    { $state_field_ident $(, $other_field_ident )* }
+		    
+		    ENUM_OUT: $enum_out
+		    
+		    DELEGATES: $delegates
+		    
+		    $( HOOKS: $hooks )?
+		    
+		    $( TRANSITIONS: $transitions )?
+		    
+		    $( TRACKED: $tracked )?
+		    
+		    $( KIND: $kind )?
+	    }
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Generics are normalized into `{ $( $gen, )* }` and `{ $( $bound )* }`
+    (@STATE_ENUM
+	    $state_ident: ident
+	    $gens: tt
+	    $bounds: tt
+	    $fields: tt
 
 	    ENUM_OUT: {
+		    $( #[@ $serde: ident] )?
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
 		    $enum_vis: vis enum $enum_ident: ident {
@@ -21,31 +212,112 @@ macro_rules! type_state_enum {
 		    }
 	    }
 	    
-	    DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
+	    DELEGATES: $delegates: tt
+	    
+	    $( HOOKS: $hooks: tt )?
+	    
+	    $( TRANSITIONS: $transitions: tt )?
+	    
+	    $( TRACKED: $tracked: tt )?
+	    
+	    $( KIND: $kind: tt )?
+    ) => {
+	    $crate::type_state_enum! {
+		    @ENUM_DEF
+		    $state_ident $gens $gens $bounds
+		    $( #[$enum_meta] )*
+		    $enum_vis enum $enum_ident { $( $var_ident ),* }
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @TRANSITIONS
+		    $state_ident $gens $bounds
+		    [ $( $hooks )? ]
+		    $( $transitions )?
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @HOOKS ($)
+		    $state_ident $gens $bounds
+		    $enum_ident { $( $var_ident ),* }
+		    [ $( $hooks )? ]
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @STATE_NAMES
+		    { $( $var_ident ),* }
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @GRAPH
+		    $enum_ident $gens $bounds
+		    { $( $var_ident ),* }
+		    $( $transitions )?
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @TRACKED
+		    $enum_ident $gens $bounds
+		    { $( $var_ident ),* }
+		    [ $( $tracked )? ]
+	    }
+	    
+	    $crate::type_state_with_state! {
+		    $state_ident $gens $bounds $fields
+	    }
+		
+		$crate::extract_variants! {
+		    $( #[@ $serde] )?
+		    #[vars( $( $all_meta ),* )]
+		    $( #[$enum_meta] )*
+			$enum_vis enum $enum_ident {
+			    $(
+			        $( [@ $ignore] )?
+			        $( #[$var_meta] )*
+			        $var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?,
+			    )*
+		    }
+	    }
+	    
+	    $crate::type_state_enum! {
+		    @ENUM_IMPLS
+		    [ $( $serde )? ]
+		    [ $( $kind )? ]
+		    $gens $bounds
+		    $enum_vis enum $enum_ident {
+			    $( $var_ident ( $crate::type_state_enum!(@STATE_TY $state_ident $gens $var_ident) ) ),*
+		    }
 		    
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
+		    DELEGATES: $delegates
 	    }
-    ) => {
+    };
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// `$state_ident<$gens, $var_ty>`
+	(@STATE_TY
+		$state_ident: ident { $( $gen: tt )* } $var_ty: ty
+	) => {
+		$state_ident<$( $gen )* $var_ty>
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// The enum itself, and `Deref`/`DerefMut` to `$state_ident<dyn Any>`
+	(@ENUM_DEF
+		$state_ident: ident $gens: tt { $( $gen: tt )* } { $( $bound: tt )* }
+		$( #[$enum_meta: meta] )*
+		$enum_vis: vis enum $enum_ident: ident { $( $var_ident: ident ),* }
+	) => {
 	    $( #[$enum_meta] )*
-		$enum_vis enum $enum_ident
+		$enum_vis enum $enum_ident<$( $gen )*>
+		where $( $bound )*
 	    {
-			$( $var_ident($state_ident<$var_ident>) ),*
+			$( $var_ident($crate::type_state_enum!(@STATE_TY $state_ident $gens $var_ident)) ),*
 		}
 
-		impl std::ops::Deref for $enum_ident {
-			type Target = $state_ident<dyn std::any::Any>;
+		impl<$( $gen )*> std::ops::Deref for $enum_ident<$( $gen )*>
+		where $( $bound )*
+		{
+			type Target = $state_ident<$( $gen )* dyn std::any::Any>;
 
 			fn deref(&self) -> &Self::Target {
 				match self {
@@ -54,180 +326,438 @@ macro_rules! type_state_enum {
 			}
 		}
 
-		impl std::ops::DerefMut for $enum_ident {
+		impl<$( $gen )*> std::ops::DerefMut for $enum_ident<$( $gen )*>
+		where $( $bound )*
+		{
 			fn deref_mut(&mut self) -> &mut Self::Target {
 				match self {
 					$( $enum_ident::$var_ident(var) => var ),*
 				}
 			}
 		}
-	    
-	    impl<Curr> $state_ident<Curr> {
-		    #[allow(clippy::needless_update)]
-		    pub fn transition_to<Next, Enum>(self, next: Next) 
-		        -> $crate::Transition<Self, Enum> where $state_ident<Next>: Into<Enum>
-		    {
-			    $crate::ChangedTo(self.with_state(next).into())
-		    }
-		    
-		    #[allow(clippy::needless_update)]
-		    pub fn with_state<Next>(self, next: Next) -> $state_ident<Next> {
-			    $state_ident::<Next> {
-				    $state_field_ident: next,
-				    ..self
-			    }
-		    }
-	    }
 		
-		$crate::extract_variants! {
-		    #[vars( $( $all_meta ),* )]
-		    $( #[$enum_meta] )*
-			$enum_vis enum $enum_ident {
-			    $(
-			        $( [@ $ignore] )?
-			        $( #[$var_meta] )*
-			        $var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?,
-			    )*
+		impl<$( $gen )*> $enum_ident<$( $gen )*>
+		where $( $bound )*
+		{
+			/// Replaces `self` with the result of `f`, returns whether the state changed.
+			///
+			/// If `f` panics the process is aborted, see `replace_with_or_abort`.
+			pub fn transition_in_place<__Curr>(&mut self, f: impl FnOnce(Self) -> $crate::Transition<__Curr, Self>) -> bool
+				where __Curr: Into<Self>
+			{
+				$crate::replace_with_or_abort(self, |this| match f(this) {
+					$crate::Unchanged(same) => (same.into(), false),
+					$crate::ChangedTo(next) => (next, true),
+				})
+			}
+			
+			/// Same as `transition_in_place`, but if `f` panics `self` is replaced with the result of `on_panic`.
+			pub fn transition_in_place_or_else<__Curr>(
+				&mut self, 
+				on_panic: impl FnOnce() -> Self, 
+				f: impl FnOnce(Self) -> $crate::Transition<__Curr, Self>,
+			) -> bool where __Curr: Into<Self> {
+				$crate::replace_with_or_else(self, on_panic, |this| match f(this) {
+					$crate::Unchanged(same) => (same.into(), false),
+					$crate::ChangedTo(next) => (next, true),
+				})
+			}
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Conversions, kind, serde and delegates, which take the generics in brackets
+	(@ENUM_IMPLS
+		$serde: tt
+		$kind: tt
+		{ $( $gen: tt )* } { $( $bound: tt )* }
+		$enum_vis: vis enum $enum_ident: ident {
+			$( $var_ident: ident ( $var_ty: ty ) ),*
+		}
+		
+		DELEGATES: $delegates: tt
+	) => {
+	    $crate::enum_variants_convert! {
+		    enum $enum_ident <[ $( $gen )* ]> where [ $( $bound )* ] {
+			    $( $var_ident ( $var_ty ) ),*
 		    }
 	    }
-	    
-	    $crate::enum_variants_convert! {
-		    enum $enum_ident {
-			    $( $var_ident ( $state_ident<$var_ident> ) ),*
+		
+	    $crate::enum_kind! {
+		    @SECTION $kind
+		    $enum_vis enum $enum_ident <[ $( $gen )* ]> where [ $( $bound )* ] {
+			    $( $var_ident ( $var_ty ) ),*
 		    }
 	    }
 		
+		$crate::serde_impls! {
+			$serde
+			@ENUM
+			$enum_ident
+			{ $( $gen )* }
+			{ $( $bound )* }
+			{ $( $var_ident ( $var_ty ) ),* }
+		}
 		    
 	    $crate::enum_delegate_impls! {
 		    ENUM_IN: {
-			    $enum_ident {
-			        $( $var_ident ( $state_ident<$var_ident> ) ),*
+			    $enum_ident <[ $( $gen )* ]> where [ $( $bound )* ] {
+			        $( $var_ident ( $var_ty ) ),*
 			    }
 		    }
 		    
-		    DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )? 
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-			    
-			    $(
-				    impl { 
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+		    DELEGATES: $delegates
 	    }
-    };
+	};
 	
 	//------------------------------------------------------------------------------------------------------------------
-	// User provided state struct
-    (@ENUM_DEF
-        { $state_gen_1: ident }
-        
-        $state_ident: ident
-        
-	    #[vars( $( $all_meta: meta ),* $(,)? )]
-	    $( #[$enum_meta: meta] )*
-	    $enum_vis: vis enum $enum_ident: ident {
-			$( $var_ident: ident ),*
-		    $(,)?
+	// No transition graph, any state may transition to any other
+	(@TRANSITIONS
+		$state_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		$hooks: tt
+	) => {
+		impl<$( $gen )* __Curr> $state_ident<$( $gen )* __Curr> where $( $bound )* {
+			$crate::type_state_enum! {
+				@TRANSITION_TO
+				$state_ident { $( $gen )* }
+				$hooks
+				[]
+			}
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Declared transition graph, only the listed edges are allowed
+	(@TRANSITIONS
+		$state_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		$hooks: tt
+		{
+			$( $from: ident => [ $( $to: ident ),* $(,)? ] ),*
+			$(,)?
+		}
+	) => {
+		$( $( impl $crate::CanTransitionTo<$to> for $from {} )* )*
+		
+		impl<$( $gen )* __Curr> $state_ident<$( $gen )* __Curr> where $( $bound )* {
+			$crate::type_state_enum! {
+				@TRANSITION_TO
+				$state_ident { $( $gen )* }
+				$hooks
+				[ __Curr: $crate::CanTransitionTo<__Next>, ]
+			}
+		}
+	};
+	
+	(@TRANSITION_TO
+		$state_ident: ident { $( $gen: tt )* }
+		[]
+		[ $( $extra_bound: tt )* ]
+	) => {
+	    pub fn transition_to<__Next, __Enum>(self, next: __Next) 
+	        -> $crate::Transition<Self, __Enum>
+	    where
+		    $( $extra_bound )*
+		    $state_ident<$( $gen )* __Next>: Into<__Enum>,
+	    {
+		    $crate::ChangedTo(self.with_state(next).into())
 	    }
-    ) => {
-	    $( #[$enum_meta] )*
-		$enum_vis enum $enum_ident
+	};
+	
+	(@TRANSITION_TO
+		$state_ident: ident { $( $gen: tt )* }
+		[ $hooks: tt ]
+		[ $( $extra_bound: tt )* ]
+	) => {
+	    pub fn transition_to<__Next, __Enum>(mut self, next: __Next) 
+	        -> $crate::Transition<Self, __Enum>
+	    where
+		    $( $extra_bound )*
+		    __Curr: $crate::StateName,
+		    __Next: $crate::StateName,
+		    Self: $crate::OnExit,
+		    $state_ident<$( $gen )* __Next>: $crate::OnEnter + Into<__Enum>,
 	    {
-			$( $var_ident($state_ident<$var_ident, $state_gen_1>) ),*
+		    $crate::OnExit::on_exit(&mut self, __Next::NAME);
+		    let mut entered = self.with_state(next);
+		    $crate::OnEnter::on_enter(&mut entered, __Curr::NAME);
+		    $crate::ChangedTo(entered.into())
+	    }
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Hooks, states that are not listed get the default (no-op) implementation
+	(@HOOKS ($d: tt)
+		$state_ident: ident $gens: tt $bounds: tt
+		$enum_ident: ident $vars: tt
+		[]
+	) => {};
+	
+	// `OnExit` listed first
+	(@HOOKS ($d: tt)
+		$state_ident: ident $gens: tt $bounds: tt
+		$enum_ident: ident $vars: tt
+		[ {
+			OnExit: $exit: tt $(,)?
+			OnEnter: $enter: tt $(,)?
+		} ]
+	) => {
+		$crate::type_state_enum! {
+			@HOOKS ($d)
+			$state_ident $gens $bounds
+			$enum_ident $vars
+			[ { OnEnter: $enter, OnExit: $exit } ]
 		}
-    };
+	};
+	
+	(@HOOKS ($d: tt)
+		$state_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		$enum_ident: ident { $( $var_ident: ident ),* }
+		[ {
+			$( OnEnter: [ $( $enter: ident ),* $(,)? ] $(,)? )?
+			$( OnExit: [ $( $exit: ident ),* $(,)? ] $(,)? )?
+		} ]
+	) => {
+		$crate::paste! {
+			macro_rules! [<__ $enum_ident _on_enter>] {
+				$( $( ( $enter ) => {}; )* )?
+				( $d var_ident: ident ) => {
+					impl<$( $gen )*> $crate::OnEnter for $state_ident<$( $gen )* $d var_ident> where $( $bound )* {}
+				};
+			}
+			
+			macro_rules! [<__ $enum_ident _on_exit>] {
+				$( $( ( $exit ) => {}; )* )?
+				( $d var_ident: ident ) => {
+					impl<$( $gen )*> $crate::OnExit for $state_ident<$( $gen )* $d var_ident> where $( $bound )* {}
+				};
+			}
+			
+			$(
+				[<__ $enum_ident _on_enter>]! { $var_ident }
+				[<__ $enum_ident _on_exit>]! { $var_ident }
+			)*
+		}
+	};
+	
+	// Implemented even without hooks, `transition_to` names the states through it when it calls the hooks
+	(@STATE_NAMES { $( $var_ident: ident ),* }) => {
+		$(
+			impl $crate::StateName for $var_ident {
+				const NAME: &'static str = stringify!($var_ident);
+			}
+		)*
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// State graph, edges are only known if the transitions were declared
+	(@GRAPH
+		$enum_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		{ $( $var_ident: ident ),* }
+		$( {
+			$( $from: ident => [ $( $to: ident ),* $(,)? ] ),*
+			$(,)?
+		} )?
+	) => {
+		impl<$( $gen )*> $enum_ident<$( $gen )*> where $( $bound )* {
+			pub const fn graph_dot() -> &'static str {
+				concat!(
+					"digraph ", stringify!($enum_ident), " {\n",
+					$( "    ", stringify!($var_ident), ";\n", )*
+					$( $( $( "    ", stringify!($from), " -> ", stringify!($to), ";\n", )* )* )?
+					"}\n",
+				)
+			}
+			
+			pub const fn graph_mermaid() -> &'static str {
+				concat!(
+					"stateDiagram-v2\n",
+					$( "    ", stringify!($var_ident), "\n", )*
+					$( $( $( "    ", stringify!($from), " --> ", stringify!($to), "\n", )* )* )?
+				)
+			}
+		}
+	};
+	
+	//------------------------------------------------------------------------------------------------------------------
+	// Wrapper that records every state change
+	(@TRACKED
+		$enum_ident: ident $gens: tt $bounds: tt
+		$vars: tt
+		[]
+	) => {};
+	
+	(@TRACKED
+		$enum_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		{ $( $var_ident: ident ),* }
+		[ {
+			$( #[$tracked_meta: meta] )*
+			$tracked_vis: vis struct $tracked_ident: ident $(;)?
+		} ]
+	) => {
+		$( #[$tracked_meta] )*
+		$tracked_vis struct $tracked_ident<$( $gen )*> where $( $bound )* {
+			state: $enum_ident<$( $gen )*>,
+			history: $crate::TransitionHistory,
+		}
+		
+		impl<$( $gen )*> $tracked_ident<$( $gen )*> where $( $bound )* {
+			/// `capacity`: How many transitions the history keeps, older ones are dropped.
+			pub fn new(state: $enum_ident<$( $gen )*>, capacity: usize) -> Self {
+				Self {
+					state,
+					history: $crate::TransitionHistory::new(capacity),
+				}
+			}
+			
+			pub const fn variant_name(state: &$enum_ident<$( $gen )*>) -> &'static str {
+				match state {
+					$( $enum_ident::$var_ident(_) => stringify!($var_ident) ),*
+				}
+			}
+			
+			pub fn get(&self) -> &$enum_ident<$( $gen )*> { &self.state }
+			
+			/// Changes made through this reference are not recorded.
+			pub fn get_mut(&mut self) -> &mut $enum_ident<$( $gen )*> { &mut self.state }
+			
+			pub fn into_inner(self) -> $enum_ident<$( $gen )*> { self.state }
+			
+			pub fn history(&self) -> &$crate::TransitionHistory { &self.history }
+			
+			pub fn clear_history(&mut self) { self.history.clear(); }
+			
+			/// Replaces the state with the result of `f`, `ChangedTo` is recorded at `tick`.
+			/// Returns whether the state changed.
+			///
+			/// If `f` panics the process is aborted, same as `transition_in_place`.
+			pub fn replace_with<__Curr>(
+				&mut self, 
+				tick: u64, 
+				f: impl FnOnce($enum_ident<$( $gen )*>) -> $crate::Transition<__Curr, $enum_ident<$( $gen )*>>,
+			) -> bool where __Curr: Into<$enum_ident<$( $gen )*>> {
+				let from = Self::variant_name(&self.state);
+				let changed = self.state.transition_in_place(f);
+				self.record(tick, from, changed);
+				changed
+			}
+			
+			/// Same as `replace_with`, but if `f` panics the state is replaced with the result of `on_panic`,
+			/// which is not recorded.
+			pub fn replace_with_or_else<__Curr>(
+				&mut self, 
+				tick: u64, 
+				on_panic: impl FnOnce() -> $enum_ident<$( $gen )*>,
+				f: impl FnOnce($enum_ident<$( $gen )*>) -> $crate::Transition<__Curr, $enum_ident<$( $gen )*>>,
+			) -> bool where __Curr: Into<$enum_ident<$( $gen )*>> {
+				let from = Self::variant_name(&self.state);
+				let changed = self.state.transition_in_place_or_else(on_panic, f);
+				self.record(tick, from, changed);
+				changed
+			}
+			
+			/// Replaces the state with `next`, recording the change at `tick` if the variant is different.
+			///
+			/// Unchecked: `next` may be any state, including the ones not declared in `TRANSITIONS`.
+			pub fn force_set(&mut self, tick: u64, next: $enum_ident<$( $gen )*>) {
+				let from = Self::variant_name(&self.state);
+				self.state = next;
+				let changed = from != Self::variant_name(&self.state);
+				self.record(tick, from, changed);
+			}
+			
+			fn record(&mut self, tick: u64, from: &'static str, changed: bool) {
+				if changed {
+					let to = Self::variant_name(&self.state);
+					self.history.push($crate::TransitionRecord { from, to, tick });
+				}
+			}
+		}
+	};
 	
 	//------------------------------------------------------------------------------------------------------------------
 	// Generated state struct
 	(
 		STATE: {
+			$( #[@ $state_serde: ident] )?
 			$( #[ $state_meta: meta ] )*
-			$state_vis: vis struct $state_ident: ident {
+			$state_vis: vis struct $state_ident: ident
+			$( <[ $( $state_gen: tt ),* $(,)? ]> )?
+			$( where [$( $state_gen_bound: tt )*] )?
+			{
 				$( $state_field_ident: ident : $state_field_ty: ty ),*
 				$(,)?
 			}
 		}
 		
-		ENUM_OUT: {
-		    #[vars( $( $all_meta: meta ),* $(,)? )]
-		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
-				$(
-					$( [@ $ignore: ident] )?
-					$( #[$var_meta: meta] )*
-					$var_ident: ident $( ( $($var_tuple: tt)* ) )? $( { $($var_fields: tt)* } )?
-			    ),*
-			    $(,)?
-		    }
-	    }
+		ENUM_OUT: $enum_out: tt
 		
-		DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )? 
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-		    
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+		DELEGATES: $delegates: tt
+		
+		$( HOOKS: $hooks: tt )?
+		
+		$( TRANSITIONS: $transitions: tt )?
+		
+		$( TRACKED: $tracked: tt )?
+		
+		$( KIND: $kind: tt )?
 	) => {
 		$( #[ $state_meta ] )*
-		$state_vis struct $state_ident<T: ?Sized> {
-			$( $state_field_ident : $state_field_ty ),*
-			state: T,
+		$state_vis struct $state_ident<$( $( $state_gen, )* )? __State: ?Sized>
+		$( where $( $state_gen_bound )* )?
+		{
+			$( $state_field_ident : $state_field_ty, )*
+			state: __State,
+		}
+		
+		$crate::serde_impls! {
+			[ $( $state_serde )? ]
+			@STRUCT
+			$state_ident
+			{ $( $( $state_gen, )* )? __State }
+			{ $( $( $state_gen_bound )* )? }
+			{ $( $state_field_ident: $state_field_ty, )* state: __State }
 		}
 		
 		$crate::type_state_enum! {
-		    STATE: $state_ident { state }
+		    @STATE_ENUM
+		    $state_ident
+		    { $( $( $state_gen, )* )? }
+		    { $( $( $state_gen_bound )* )? }
+		    { state $(, $state_field_ident )* }
 			
-			ENUM_OUT: {
-			    #[vars( $( $all_meta ),* $(,)? )]
-			    $( #[$enum_meta] )*
-			    $enum_vis enum $enum_ident {
-					$(
-						$( [@ $ignore] )?
-						$( #[$var_meta] )*
-						$var_ident: $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?
-				    ),*
-				    $(,)?
-			    }
-		    }
+			ENUM_OUT: $enum_out
 			
-			DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )? 
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-			    
-			    $(
-				    impl { 
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+			DELEGATES: $delegates
+			
+			$( HOOKS: $hooks )?
+			
+			$( TRANSITIONS: $transitions )?
+			
+			$( TRACKED: $tracked )?
+			
+			$( KIND: $kind )?
 	    }
 	};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! type_state_with_state {
+	(
+		$state_ident: ident { $( $gen: tt )* } { $( $bound: tt )* }
+		{ $state_field_ident: ident $(, $other_field_ident: ident )* }
+	) => {
+		impl<$( $gen )* __Curr> $state_ident<$( $gen )* __Curr> where $( $bound )* {
+		    pub fn with_state<__Next>(self, next: __Next) -> $state_ident<$( $gen )* __Next> {
+			    $state_ident {
+				    $state_field_ident: next,
+				    $( $other_field_ident: self.$other_field_ident, )*
+			    }
+		    }
+		}
+	};
+}
+
 #[cfg(test)]
 #[allow(unused)]
 #[allow(non_camel_case_types)]
@@ -308,84 +838,456 @@ mod test {
 	}
 }
 
-/*
+/// Shared by the tests below: a generic state struct with every optional section.
 #[cfg(test)]
 #[allow(unused)]
-#[allow(non_camel_case_types)]
-mod test_1 {
+mod villager {
 	use crate::transition_result::Transition;
+	use crate::{OnEnter, OnExit};
 
-	#[derive(Clone, Debug)]
-	pub struct State<T: ?Sized + 'static> {
-		state: T,
+	pub trait World {
+		fn danger(&self) -> f32;
 	}
 
-	#[derive(Debug, Clone)]
-	pub struct CustomType;
+	pub struct Village(pub f32);
+
+	impl World for Village {
+		fn danger(&self) -> f32 { self.0 }
+	}
+
+	pub static VILLAGE: Village = Village(0.9);
+
+	pub struct Villager<'w, W, S: ?Sized>
+	where W: World
+	{
+		pub world: &'w W,
+		pub hunger: f32,
+		pub events: Vec<String>,
+		pub state: S,
+	}
 
 	type_state_enum! {
-		STATE: State where [Next: 'static] { state }
+		STATE: Villager<['w, W]> where [W: World] { state, world, hunger, events }
 		
 		ENUM_OUT: {
-			#[vars(derive(Clone, Debug))]
-			#[derive(Debug, Clone)]
-			pub enum StateEnum {
-				#[derive(PartialEq)] 
-				 Int { field: i32 },
-				 UInt {
-					 x: i32,
-					 y: i32,
-				 },
-				 Float(f32, i32),
-				 [@SKIP]
-				 CustomType,
-				 Unit,
+			#[vars(derive(Debug))]
+			pub enum VillagerEnum {
+				Idle,
+				Hungry { since: f32 },
+				Fleeing { from: f32 },
 			}
 		}
 		
-		DELEGATES: { 
-			impl trait Tick {
-				[fn tick(&mut self, delta_time: f64);]
+		DELEGATES: {
+			impl<['w, W]> trait Tick<'w, W> where [W: World] {
+				[fn tick(self) -> VillagerEnum<'w, W>]
 			}
 		}
+		
+		HOOKS: {
+			OnEnter: [Hungry],
+			OnExit: [Idle, Hungry],
+		}
+		
+		TRANSITIONS: {
+			Idle => [Hungry, Fleeing],
+			Hungry => [Idle],
+			Fleeing => [Idle],
+		}
+		
+		TRACKED: {
+			pub struct VillagerTracked;
+		}
+		
+		KIND: {
+			pub enum VillagerKind;
+		}
 	}
 
-	fn test(mut x: State<Int>) {
-		let t: Transition<State<Int>, StateEnum> = x.transition_to(Unit);
+	pub fn idle<W: World>(world: &W) -> Villager<'_, W, Idle> {
+		Villager { world, hunger: 0.9, events: Vec::new(), state: Idle }
 	}
 
-	trait Tick {
-		fn tick(&mut self, delta_time: f64);
+	pub trait Tick<'w, W: World> {
+		fn tick(self) -> VillagerEnum<'w, W>;
 	}
 
-	impl Tick for State<Int> {
-		fn tick(&mut self, delta_time: f64) {
-			todo!()
+	impl<'w, W: World> Tick<'w, W> for Villager<'w, W, Idle> {
+		fn tick(self) -> VillagerEnum<'w, W> {
+			let danger = self.world.danger();
+			
+			if danger > 0.5 {
+				self.transition_to(Fleeing { from: danger }).into_enum()
+			} else {
+				self.into()
+			}
 		}
 	}
 
-	impl Tick for State<UInt> {
-		fn tick(&mut self, delta_time: f64) {
-			todo!()
+	impl<'w, W: World> Tick<'w, W> for Villager<'w, W, Hungry> {
+		fn tick(self) -> VillagerEnum<'w, W> { self.into() }
+	}
+
+	impl<'w, W: World> Tick<'w, W> for Villager<'w, W, Fleeing> {
+		fn tick(self) -> VillagerEnum<'w, W> { self.transition_to(Idle).into_enum() }
+	}
+	
+	impl<W: World> OnEnter for Villager<'_, W, Hungry> {
+		fn on_enter(&mut self, from: &'static str) {
+			self.hunger = 0.0;
+			self.events.push(format!("enter Hungry from {from}"));
 		}
 	}
+	
+	impl<W: World> OnExit for Villager<'_, W, Idle> {
+		fn on_exit(&mut self, to: &'static str) { self.events.push(format!("exit Idle to {to}")); }
+	}
+	
+	impl<W: World> OnExit for Villager<'_, W, Hungry> {
+		fn on_exit(&mut self, to: &'static str) { self.events.push(format!("exit Hungry to {to}")); }
+	}
+}
 
-	impl Tick for State<Float> {
-		fn tick(&mut self, delta_time: f64) {
-			todo!()
+#[cfg(test)]
+#[allow(unused)]
+mod test_transitions {
+	use super::villager::*;
+	use crate::transition_result::Transition;
+
+	#[test]
+	fn test() {
+		let hungry: Transition<_, VillagerEnum<_>> = idle(&VILLAGE).transition_to(Hungry { since: 2.0 });
+		
+		let Transition::ChangedTo(VillagerEnum::Hungry(hungry)) = hungry
+		else { panic!("expected `Hungry`") };
+		
+		assert_eq!(hungry.state.since, 2.0);
+	}
+	
+	#[test]
+	fn in_place() {
+		struct Component {
+			villager: VillagerEnum<'static, Village>,
 		}
+		
+		let mut component = Component { villager: idle(&VILLAGE).into() };
+		
+		let changed = component.villager.transition_in_place(|villager| match villager {
+			VillagerEnum::Idle(idle) if idle.hunger > 0.5 => idle.transition_to(Hungry { since: 1.0 }).map_unchanged(Into::into),
+			other => Transition::Unchanged(other),
+		});
+		
+		assert!(changed);
+		assert!(matches!(component.villager, VillagerEnum::Hungry(_)));
+		assert!(!component.villager.transition_in_place(Transition::<VillagerEnum<_>, _>::Unchanged));
+		
+		let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			component.villager.transition_in_place_or_else(
+				|| idle(&VILLAGE).into(),
+				|_| -> Transition<VillagerEnum<_>, _> { panic!("tick failed") },
+			)
+		}));
+		
+		assert!(panicked.is_err());
+		assert!(matches!(component.villager, VillagerEnum::Idle(_)));
 	}
+	
+	#[test]
+	fn graph() {
+		assert_eq!(
+			VillagerEnum::<Village>::graph_dot(),
+			"digraph VillagerEnum {\n    \
+			    Idle;\n    \
+			    Hungry;\n    \
+			    Fleeing;\n    \
+			    Idle -> Hungry;\n    \
+			    Idle -> Fleeing;\n    \
+			    Hungry -> Idle;\n    \
+			    Fleeing -> Idle;\n\
+			}\n"
+		);
+		
+		assert_eq!(
+			VillagerEnum::<Village>::graph_mermaid(),
+			"stateDiagram-v2\n    \
+			    Idle\n    \
+			    Hungry\n    \
+			    Fleeing\n    \
+			    Idle --> Hungry\n    \
+			    Idle --> Fleeing\n    \
+			    Hungry --> Idle\n    \
+			    Fleeing --> Idle\n"
+		);
+	}
+}
 
-	impl Tick for State<CustomType> {
-		fn tick(&mut self, delta_time: f64) {
-			todo!()
+#[cfg(test)]
+#[allow(unused)]
+mod test_hooks {
+	use super::villager::*;
+	use crate::transition_result::Transition;
+
+	#[test]
+	fn test() {
+		let Transition::ChangedTo(VillagerEnum::Hungry(hungry)) = idle(&VILLAGE).transition_to(Hungry { since: 0.0 })
+		else { panic!("expected `Hungry`") };
+		
+		assert_eq!(hungry.hunger, 0.0);
+		assert_eq!(hungry.events, ["exit Idle to Hungry", "enter Hungry from Idle"]);
+		
+		let Transition::ChangedTo(VillagerEnum::Idle(idle)) = hungry.transition_to(Idle)
+		else { panic!("expected `Idle`") };
+		
+		let Transition::ChangedTo(VillagerEnum::Fleeing(fleeing)) = idle.transition_to(Fleeing { from: 0.0 })
+		else { panic!("expected `Fleeing`") };
+		
+		assert_eq!(
+			fleeing.events,
+			["exit Idle to Hungry", "enter Hungry from Idle", "exit Hungry to Idle", "exit Idle to Fleeing"]
+		);
+	}
+
+	type_state_enum! {
+		STATE: {
+			pub struct Door {
+				log: Vec<&'static str>,
+			}
+		}
+		
+		ENUM_OUT: {
+			#[vars()]
+			pub enum DoorEnum {
+				Open,
+				Closed,
+			}
+		}
+		
+		DELEGATES: {}
+		
+		HOOKS: {
+			OnExit: [Open],
+			OnEnter: [Open],
 		}
 	}
 
-	impl Tick for State<Unit> {
-		fn tick(&mut self, delta_time: f64) {
-			todo!()
+	impl crate::OnExit for Door<Open> {
+		fn on_exit(&mut self, to: &'static str) { self.log.push(to); }
+	}
+
+	impl crate::OnEnter for Door<Open> {
+		fn on_enter(&mut self, from: &'static str) { self.log.push(from); }
+	}
+
+	#[test]
+	fn on_exit_first() {
+		let closed = Door { log: Vec::new(), state: Closed };
+		let Transition::ChangedTo(DoorEnum::Open(open)) = closed.transition_to(Open)
+		else { panic!("expected `Open`") };
+
+		let Transition::ChangedTo(DoorEnum::Closed(closed)) = open.transition_to(Closed)
+		else { panic!("expected `Closed`") };
+
+		assert_eq!(closed.log, ["Closed", "Closed"]);
+	}
+}
+
+#[cfg(test)]
+#[allow(unused)]
+mod test_tracked {
+	use crate::transition_result::Transition;
+	use crate::TransitionRecord;
+
+	type_state_enum! {
+		STATE: {
+			#[derive(Debug)]
+			pub struct Order {
+				id: u32,
+			}
+		}
+		
+		ENUM_OUT: {
+			#[vars(derive(Debug))]
+			#[derive(Debug)]
+			pub enum OrderEnum {
+				Placed,
+				Paid,
+				Shipped,
+			}
+		}
+		
+		DELEGATES: {}
+		
+		TRANSITIONS: {
+			Placed => [Paid],
+			Paid => [Shipped],
 		}
+		
+		TRACKED: {
+			#[derive(Debug)]
+			pub struct OrderTracked;
+		}
+	}
+	
+	fn advance(order: OrderEnum) -> Transition<OrderEnum, OrderEnum> {
+		match order {
+			OrderEnum::Placed(placed) => placed.transition_to(Paid).map_unchanged(Into::into),
+			OrderEnum::Paid(paid) => paid.transition_to(Shipped).map_unchanged(Into::into),
+			shipped => Transition::Unchanged(shipped),
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut order = OrderTracked::new(Order { id: 7, state: Placed }.into(), 2);
+		
+		assert!(order.replace_with(10, advance));
+		assert!(order.replace_with(20, advance));
+		assert!(!order.replace_with(30, advance));
+		assert!(matches!(order.get(), OrderEnum::Shipped(_)));
+		
+		assert_eq!(
+			order.history().iter().copied().collect::<Vec<_>>(),
+			[
+				TransitionRecord { from: "Placed", to: "Paid", tick: 10 },
+				TransitionRecord { from: "Paid", to: "Shipped", tick: 20 },
+			]
+		);
+		
+		order.clear_history();
+		assert!(order.history().is_empty());
+		assert_eq!(order.into_inner().id, 7);
+	}
+	
+	#[test]
+	fn force_set() {
+		let mut order = OrderTracked::new(Order { id: 8, state: Placed }.into(), 2);
+		
+		order.force_set(10, Order { id: 8, state: Paid }.into());
+		assert_eq!(order.history().last(), Some(&TransitionRecord { from: "Placed", to: "Paid", tick: 10 }));
+		
+		// Same variant, not a transition
+		order.force_set(11, Order { id: 9, state: Paid }.into());
+		assert_eq!(order.history().len(), 1);
+		assert_eq!(order.get().id, 9);
+	}
+}
+
+#[cfg(test)]
+#[allow(unused)]
+mod test_generics {
+	use super::villager::*;
+	use crate::transition_result::Transition;
+
+	#[test]
+	fn test() {
+		let calm = Village(0.1);
+		let villager: VillagerEnum<Village> = idle(&calm).into();
+		assert_eq!(villager.kind(), VillagerKind::Idle);
+		assert!(matches!(villager.tick(), VillagerEnum::Idle(_)));
+
+		let VillagerEnum::Fleeing(fleeing) = idle(&VILLAGE).tick()
+		else { panic!("expected `Fleeing`") };
+
+		assert_eq!(fleeing.state.from, 0.9);
+
+		let mut tracked = VillagerTracked::new(fleeing.into(), 4);
+		tracked.replace_with(1, |villager| Transition::<VillagerEnum<_>, _>::ChangedTo(villager.tick()));
+		assert_eq!(tracked.get().kind(), VillagerKind::Idle);
+		assert_eq!(tracked.history().last().map(|record| (record.from, record.to)), Some(("Fleeing", "Idle")));
+	}
+
+	type_state_enum! {
+		STATE: {
+			pub struct Counter<[N]> where [N: Copy + Default] {
+				count: N,
+			}
+		}
+		
+		ENUM_OUT: {
+			#[vars()]
+			pub enum CounterEnum {
+				Running,
+				Stopped,
+			}
+		}
+		
+		DELEGATES: {
+			impl<[N: Copy + Default]> trait Label via .state {
+				[fn label(&self) -> &'static str]
+			}
+		}
+	}
+
+	trait Label {
+		fn label(&self) -> &'static str;
+	}
+
+	impl Label for Running {
+		fn label(&self) -> &'static str { "running" }
+	}
+
+	impl Label for Stopped {
+		fn label(&self) -> &'static str { "stopped" }
+	}
+
+	#[test]
+	fn generated() {
+		let running = Counter { count: 3_u8, state: Running };
+		let Transition::ChangedTo(CounterEnum::Stopped(stopped)) = running.transition_to(Stopped)
+		else { panic!("expected `Stopped`") };
+
+		assert_eq!(stopped.count, 3);
+		assert_eq!(CounterEnum::from(stopped).label(), "stopped");
+		assert_eq!(<Running as crate::StateName>::NAME, "Running");
+	}
+}
+
+#[cfg(test)]
+#[allow(unused)]
+mod test_reserved_generics {
+	use crate::transition_result::Transition;
+
+	type_state_enum! {
+		STATE: {
+			pub struct Gauge<[T]> where [T: Copy] {
+				level: T,
+			}
+		}
+		
+		ENUM_OUT: {
+			#[vars()]
+			pub enum GaugeEnum {
+				Filling,
+				Full,
+			}
+		}
+		
+		DELEGATES: {}
+		
+		HOOKS: {}
+		
+		TRANSITIONS: {
+			Filling => [Full],
+		}
+		
+		TRACKED: {
+			pub struct GaugeTracked;
+		}
+	}
+
+	#[test]
+	fn test() {
+		let filling = Gauge { level: 0.5_f32, state: Filling };
+		let mut tracked = GaugeTracked::new(filling.into(), 1);
+		
+		assert!(tracked.replace_with(1, |gauge| match gauge {
+			GaugeEnum::Filling(filling) => filling.transition_to(Full).map_unchanged(Into::into),
+			full => Transition::Unchanged(full),
+		}));
+		
+		let GaugeEnum::Full(full) = tracked.into_inner()
+		else { panic!("expected `Full`") };
+		
+		assert_eq!(full.level, 0.5);
 	}
 }
-*/
@@ -4,20 +4,27 @@ macro_rules! type_table {
 	// Table + Generated enum
 	(
 		ENUM_OUT: {
+		    $( #[@ $serde: ident] )?
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
 					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
 					$( ( $($var_tuple: tt)* ) )?
 					$( { $($var_fields: tt)* } )?
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
 	    }
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident $(;)? $({})?
 		}
@@ -26,20 +33,32 @@ macro_rules! type_table {
 			ENUM_IN: $enum_ident;
 
 			TABLE: {
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
-				$table_vis struct $table_ident {
-				    $( $var_ident: $var_ident ),*
+				$table_vis struct $table_ident
+				$( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+				{
+				    $( $var_ident $( <[ $( $var_gen )* ]> )?: $var_ident $( <$( $var_gen )*> )? ),*
 			    }
 			}
 		}
 
 		$crate::extract_variants_into_enum! {
+			$( #[@ $serde] )?
 			#[vars( $( $all_meta ),* )]
 			$( #[$enum_meta] )*
-			$enum_vis enum $enum_ident {
+			$enum_vis enum $enum_ident
+			$( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+			{
 			    $(
 			        $( #[$var_meta] )*
-			        $var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?
+			        $var_ident
+			        $( <[ $( $var_gen )* ]> )?
+			        $( ( $($var_tuple)* ) )?
+			        $( { $($var_fields)* } )?
+			        $( where [ $( $var_bound )* ] )?
 			    ),*
 		    }
 		}
@@ -49,52 +68,55 @@ macro_rules! type_table {
 	// Table + Generated enum + Delegates
 	(
 		ENUM_OUT: {
+		    $( #[@ $serde: ident] )?
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
-					$var_ident: ident $( ( $($var_tuple: tt)* ) )? $( { $($var_fields: tt)* } )?
+					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
+					$( ( $($var_tuple: tt)* ) )?
+					$( { $($var_fields: tt)* } )?
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
 	    }
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident $(;)? $({})?
 		}
 
-		DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )?
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
-
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+		DELEGATES: $delegates: tt
 	) => {
 		$crate::type_table! {
 			ENUM_OUT: {
+			    $( #[@ $serde] )?
 			    #[vars( $( $all_meta ),* )]
 			    $( #[$enum_meta] )*
-			    $enum_vis enum $enum_ident {
+			    $enum_vis enum $enum_ident
+			    $( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+			    {
 					$(
 						$( #[$var_meta] )*
-						$var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?
+						$var_ident
+						$( <[ $( $var_gen )* ]> )?
+						$( ( $($var_tuple)* ) )?
+						$( { $($var_fields)* } )?
+						$( where [ $( $var_bound )* ] )?
 				    ),*
 			    }
 		    }
 
 			TABLE: {
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
 				$table_vis struct $table_ident
 			}
@@ -102,39 +124,31 @@ macro_rules! type_table {
 
 		$crate::enum_delegate_impls! {
 			ENUM_IN: {
-				$enum_ident {
-					$( $var_ident ( $var_ident ) ),*
+				$enum_ident
+				$( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+				{
+					$( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
 			    }
 		    }
 
-			DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )?
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-
-			    $(
-				    impl {
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+			DELEGATES: $delegates
 		}
 	};
 
 	//------------------------------------------------------------------------------------------------------------------
-	// Table + User enum
+	// Table + User enum, the generics of the table must be the same as the enum's
 	(
 		ENUM_IN: $enum_ident: ident $(;)? $({})?
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
-			$table_vis: vis struct $table_ident: ident {
-			    $( $var_ident: ident: $var_ty: ty ),*
+			$table_vis: vis struct $table_ident: ident
+			$( <[ $( $gen: tt )* ]> )?
+			$( where [ $( $bound: tt )* ] )?
+			{
+			    $( $var_ident: ident $( <[ $( $var_gen: tt )* ]> )?: $var_ty: ty ),*
 			    $(,)?
 		    }
 		}
@@ -143,16 +157,22 @@ macro_rules! type_table {
 			@TABLE_INTERNAL
 			$enum_ident
 
+			$( #[@ $table_serde] )?
 			$( #[$table_meta] )*
-			$table_vis struct $table_ident {
-			    $( $var_ident: $var_ty ),*
+			$table_vis struct $table_ident
+			[ $( $( $gen )* )? ]
+			[ $( $( $bound )* )? ]
+			{
+			    $( $var_ident [ $( $( $var_gen )* )? ]: $var_ty ),*
 		    }
 		}
 
 		$crate::paste! {
-			impl IntoIterator for $table_ident {
-			    type Item = $enum_ident;
-				type IntoIter = core::array::IntoIter<$enum_ident, { $table_ident::LENGTH }>;
+			impl $( <$( $gen )*> )? IntoIterator for $table_ident $( <$( $gen )*> )?
+			$( where $( $bound )* )?
+			{
+			    type Item = $enum_ident $( <$( $gen )*> )?;
+				type IntoIter = core::array::IntoIter<Self::Item, { $crate::count_idents!( $( $var_ident )* ) }>;
 
 				fn into_iter(self) -> Self::IntoIter {
 					[ $( $enum_ident::$var_ident(self.[<$var_ident:snake:lower>].into()) ),* ].into_iter()
@@ -163,9 +183,13 @@ macro_rules! type_table {
 
 	// Table only
 	(
+		$( #[@ $table_serde: ident] )?
 		$( #[$table_meta: meta] )*
-		$table_vis: vis struct $table_ident: ident {
-		    $( $var_ident: ident: $var_ty: ty ),*
+		$table_vis: vis struct $table_ident: ident
+		$( <[ $( $gen: tt )* ]> )?
+		$( where [ $( $bound: tt )* ] )?
+		{
+		    $( $var_ident: ident $( <[ $( $var_gen: tt )* ]> )?: $var_ty: ty ),*
 		    $(,)?
 	    }
 	) => {
@@ -174,9 +198,13 @@ macro_rules! type_table {
 				@TABLE_INTERNAL
 				[<$table_ident Member>]
 
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
-				$table_vis struct $table_ident {
-				    $( $var_ident: $var_ty ),*
+				$table_vis struct $table_ident
+				[ $( $( $gen )* )? ]
+				[ $( $( $bound )* )? ]
+				{
+				    $( $var_ident [ $( $( $var_gen )* )? ]: $var_ty ),*
 			    }
 			}
 		}
@@ -188,42 +216,57 @@ macro_rules! type_table {
 		@TABLE_INTERNAL
 		$enum_ident: ident
 
+		$( #[@ $table_serde: ident] )?
 		$( #[$table_meta: meta] )*
-		$table_vis: vis struct $table_ident: ident {
-		    $( $var_ident: ident: $var_ty: ty ),*
+		$table_vis: vis struct $table_ident: ident
+		[ $( $gen: tt )* ]
+		[ $( $bound: tt )* ]
+		{
+		    $( $var_ident: ident $var_gen: tt : $var_ty: ty ),*
 		    $(,)?
 	    }
 	) => {
 		$crate::paste! {
 			$( #[$table_meta] )*
-		    $table_vis struct $table_ident {
+		    $table_vis struct $table_ident<$( $gen )*>
+			where $( $bound )*
+			{
 		        $([<$var_ident:snake:lower>]: $var_ty),*
 		    }
 
-			$table_vis enum [<$enum_ident Ref>]<'a> {
-			    $($var_ident(&'a $var_ty)),*
-		    }
+			$crate::serde_impls! {
+				[ $( $table_serde )? ]
+				@TABLE
+				$table_ident
+				{ $( $gen )* }
+				{ $( $bound )* }
+				{ $( $var_ident => [<$var_ident:snake:lower>]: $var_ty ),* }
+			}
 
-		    $table_vis enum [<$enum_ident Mut>]<'a> {
-			    $($var_ident(&'a mut $var_ty)),*
+			$table_vis enum [<$enum_ident Ref>]<'__a, $( $gen )*>
+			where $( $bound )*
+			{
+			    $($var_ident(&'__a $var_ty)),*
 		    }
 
-		    $(
-			    impl $crate::MemberOf<$table_ident> for $var_ident {
-				    type MemberType = $var_ty;
-
-		            fn get_in_table(table: & $table_ident) -> &Self::MemberType {
-		                &table.[<$var_ident:snake:lower>]
-		            }
+		    $table_vis enum [<$enum_ident Mut>]<'__a, $( $gen )*>
+			where $( $bound )*
+			{
+			    $($var_ident(&'__a mut $var_ty)),*
+		    }
 
-		            fn get_in_table_mut(table: &mut $table_ident) -> &mut Self::MemberType {
-		                &mut table.[<$var_ident:snake:lower>]
-		            }
-		        }
-		    )*
+			$crate::type_table! {
+				@MEMBER_OF_IMPLS
+				[ $( $gen )* ]
+				[ $( $bound )* ]
+				$table_ident
+				{ $( $var_ident $var_gen: $var_ty ),* }
+			}
 
-			impl $table_ident {
-				pub const LENGTH: usize = ${count($var_ident)};
+			impl<$( $gen )*> $table_ident<$( $gen )*>
+			where $( $bound )*
+			{
+				pub const LENGTH: usize = $crate::count_idents!( $( $var_ident )* );
 
 				pub fn get<Member: $crate::MemberOf<Self>>(&self) -> &Member::MemberType {
 				    Member::get_in_table(self)
@@ -241,14 +284,14 @@ macro_rules! type_table {
 			    }
 
 				#[allow(clippy::needless_lifetimes)]
-				pub fn iter<'a>(&'a self) -> impl Iterator<Item = [<$enum_ident Ref>]<'a>> {
+				pub fn iter<'__a>(&'__a self) -> impl Iterator<Item = [<$enum_ident Ref>]<'__a, $( $gen )*>> {
 					[
 						$( [<$enum_ident Ref>]::$var_ident(&self.[<$var_ident:snake:lower>]) ),*
 					].into_iter()
 				}
 
 				#[allow(clippy::needless_lifetimes)]
-				pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = [<$enum_ident Mut>]<'a>> {
+				pub fn iter_mut<'__a>(&'__a mut self) -> impl Iterator<Item = [<$enum_ident Mut>]<'__a, $( $gen )*>> {
 					[
 						$( [<$enum_ident Mut>]::$var_ident(&mut self.[<$var_ident:snake:lower>]) ),*
 					].into_iter()
@@ -256,6 +299,52 @@ macro_rules! type_table {
 			}
 		}
 	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// MemberOf impls, the members are keyed by their own generics: `Gold<T>`
+	(
+		@MEMBER_OF_IMPLS
+		$gens: tt
+		$bounds: tt
+		$table_ident: ident
+		{ $( $var_ident: ident $var_gen: tt : $var_ty: ty ),* }
+	) => {
+		$(
+			$crate::type_table! {
+				@MEMBER_OF_IMPL
+				$gens
+				$bounds
+				$table_ident
+				$var_ident $var_gen: $var_ty
+			}
+		)*
+	};
+
+	(
+		@MEMBER_OF_IMPL
+		[ $( $gen: tt )* ]
+		[ $( $bound: tt )* ]
+		$table_ident: ident
+		$var_ident: ident [ $( $var_gen: tt )* ]: $var_ty: ty
+	) => {
+		$crate::paste! {
+			impl<$( $gen )*> $crate::MemberOf<$table_ident<$( $gen )*>> for $var_ident<$( $var_gen )*>
+			where $( $bound )*
+			{
+			    type MemberType = $var_ty;
+
+	            #[allow(clippy::needless_lifetimes)]
+	            fn get_in_table<'__t>(table: &'__t $table_ident<$( $gen )*>) -> &'__t Self::MemberType {
+	                &table.[<$var_ident:snake:lower>]
+	            }
+
+	            #[allow(clippy::needless_lifetimes)]
+	            fn get_in_table_mut<'__t>(table: &'__t mut $table_ident<$( $gen )*>) -> &'__t mut Self::MemberType {
+	                &mut table.[<$var_ident:snake:lower>]
+	            }
+	        }
+		}
+	};
 }
 
 #[allow(unused)]
@@ -364,3 +453,65 @@ mod tests_3 {
 		}
 	}
 }
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_generics {
+	use std::fmt::{Debug, Formatter};
+
+	use crate::type_table;
+
+	type_table! {
+		ENUM_OUT: {
+			#[vars(derive(Debug, Clone, PartialEq))]
+			pub enum Resource<['a, T]> where [T: Copy] {
+				Gold<[T]>(T),
+				Wood<[T]>(T) where [T: Copy],
+				Label<['a]>(&'a str),
+				Empty,
+			}
+		}
+
+		TABLE: {
+			#[derive(Debug, Clone)]
+			pub struct ResourceTable;
+		}
+
+		DELEGATES: {
+			impl<['a, T]> trait Debug where [T: Copy + Debug] {
+				[fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error>]
+			}
+		}
+	}
+
+	#[derive(Debug, Clone, PartialEq)]
+	pub struct Stock<T>(T);
+
+	type_table! {
+		#[derive(Debug, Clone)]
+		pub struct StockTable<[T]> where [T: Clone] {
+			Stock<[T]>: Stock<T>,
+			Count: usize,
+		}
+	}
+
+	pub struct Count;
+
+	#[test]
+	fn test() {
+		let mut table = ResourceTable::new(Gold(5), Wood(3), Label("stock"), Empty);
+		assert_eq!(table.get::<Gold<_>>(), &Gold(5));
+		assert_eq!(table.get::<Label>(), &Label("stock"));
+
+		table.get_mut::<Wood<i32>>().0 += 2;
+		assert_eq!(table.get::<Wood<_>>(), &Wood(5));
+		assert_eq!(table.iter().count(), ResourceTable::<i32>::LENGTH);
+
+		let debug = table.into_iter().map(|res| format!("{res:?}")).collect::<Vec<_>>();
+		assert_eq!(debug, ["Gold(5)", "Wood(5)", "Label(\"stock\")", "Empty"]);
+
+		let stock = StockTable::new(Stock(1.5), 2);
+		assert_eq!(stock.get::<Stock<f64>>(), &Stock(1.5));
+		assert_eq!(stock.get::<Count>(), &2);
+	}
+}
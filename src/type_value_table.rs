@@ -4,42 +4,83 @@ macro_rules! type_value_table {
 	// Table + Generated enum
 	(
 		ENUM_OUT: {
+		    $( #[@ $serde: ident] )?
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
-					$var_ident: ident $( ( $($var_tuple: tt)* ) )? $( { $($var_fields: tt)* } )?
+					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
+					$( ( $($var_tuple: tt)* ) )?
+					$( { $($var_fields: tt)* } )?
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
 	    }
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident < $gen: ident >
 			$( where [ $( $bounds: tt )* ] )?
 			$(;)? $({})?
 		}
+
+		$(
+			KIND: {
+				$( #[$kind_meta: meta] )*
+				$kind_vis: vis enum $kind_ident: ident $(;)?
+			}
+		)?
+
+		$( OPS: $ops: tt )?
 	) => {
 		$crate::type_value_table! {
 			ENUM_IN: $enum_ident;
 
 			TABLE: {
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
 				$table_vis struct $table_ident < $gen > $( where [ $( $bounds )* ] )? {
-				    $( $var_ident ),*
+				    $( $var_ident $( <[ $( $var_gen )* ]> )? $( where [ $( $var_bound )* ] )? ),*
 			    }
 			}
+
+			$( KIND: $kind_ident; )?
+
+			$( OPS: $ops )?
+		}
+
+		$crate::enum_kind! {
+			@SECTION [ $( { $( #[$kind_meta] )* $kind_vis enum $kind_ident } )? ]
+			$enum_vis enum $enum_ident
+			$( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+			{
+				$( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
+			}
 		}
 
 		$crate::extract_variants_into_enum! {
+			$( #[@ $serde] )?
 			#[vars( $( $all_meta ),* )]
 			$( #[$enum_meta] )*
-			$enum_vis enum $enum_ident {
+			$enum_vis enum $enum_ident
+			$( <[ $( $enum_gen )* ]> )?
+			$( where [ $( $enum_bound )* ] )?
+			{
 			    $(
 			        $( #[$var_meta] )*
-			        $var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?
+			        $var_ident
+			        $( <[ $( $var_gen )* ]> )?
+			        $( ( $($var_tuple)* ) )?
+			        $( { $($var_fields)* } )?
+			        $( where [ $( $var_bound )* ] )?
 			    ),*
 		    }
 		}
@@ -49,82 +90,81 @@ macro_rules! type_value_table {
 	// Table + Generated enum + Delegates
 	(
 		ENUM_OUT: {
+		    $( #[@ $serde: ident] )?
 		    #[vars( $( $all_meta: meta ),* $(,)? )]
 		    $( #[$enum_meta: meta] )*
-		    $enum_vis: vis enum $enum_ident: ident {
+		    $enum_vis: vis enum $enum_ident: ident
+		    $( <[ $( $enum_gen: tt )* ]> )?
+			$( where [ $( $enum_bound: tt )* ] )?
+		    {
 				$(
 					$( #[$var_meta: meta] )*
-					$var_ident: ident $( ( $($var_tuple: tt)* ) )? $( { $($var_fields: tt)* } )?
+					$var_ident: ident
+					$( <[ $( $var_gen: tt )* ]> )?
+					$( ( $($var_tuple: tt)* ) )?
+					$( { $($var_fields: tt)* } )?
+					$( where [ $( $var_bound: tt )* ] )?
 			    ),*
 			    $(,)?
 		    }
 	    }
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident < $gen: ident >
 			$( where [ $( $bounds: tt )* ] )?
 			$(;)? $({})?
 		}
 
-		DELEGATES: {
-		    $(
-		        impl $( <[ $( $trait_gen: tt )*  ]> )?
-		        trait $trait_ty: path
-		        $( where [ $( $trait_bound: tt )* ] )?
-		        {
-				    $( [ $( $item: tt )* ] )*
-			    }
-		    )*
+		DELEGATES: $delegates: tt
 
-		    $(
-			    impl {
-			        $( [ $( $std_impl: tt )* ] )*
-			    }
-		    )?
-	    }
+		$( KIND: $kind: tt )?
+
+		$( OPS: $ops: tt )?
 	) => {
 		$crate::type_value_table! {
 			ENUM_OUT: {
+			    $( #[@ $serde] )?
 			    #[vars( $( $all_meta ),* )]
 			    $( #[$enum_meta] )*
-			    $enum_vis enum $enum_ident {
+			    $enum_vis enum $enum_ident
+			    $( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+			    {
 					$(
 						$( #[$var_meta] )*
-						$var_ident $( ( $($var_tuple)* ) )? $( { $($var_fields)* } )?
+						$var_ident
+						$( <[ $( $var_gen )* ]> )?
+						$( ( $($var_tuple)* ) )?
+						$( { $($var_fields)* } )?
+						$( where [ $( $var_bound )* ] )?
 				    ),*
 			    }
 		    }
 
 			TABLE: {
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
 				$table_vis struct $table_ident< $gen > $( where [ $( $bounds )* ] )?
 			}
+
+			$( KIND: $kind )?
+
+			$( OPS: $ops )?
 		}
 
 		$crate::enum_delegate_impls! {
 			ENUM_IN: {
-				$enum_ident {
-					$( $var_ident ( $var_ident ) ),*
+				$enum_ident
+				$( <[ $( $enum_gen )* ]> )?
+				$( where [ $( $enum_bound )* ] )?
+				{
+					$( $var_ident ( $var_ident $( <$( $var_gen )*> )? ) ),*
 			    }
 		    }
 
-			DELEGATES: {
-			    $(
-			        impl $( <[ $( $trait_gen )*  ]> )?
-				    trait $trait_ty
-			        $( where [ $( $trait_bound )* ] )?
-			        {
-					    $( [ $( $item )* ] )*
-				    }
-			    )*
-
-			    $(
-				    impl {
-					    $( [ $( $std_impl )* ] )*
-				    }
-			    )?
-		    }
+			DELEGATES: $delegates
 		}
 	};
 
@@ -134,50 +174,74 @@ macro_rules! type_value_table {
 		ENUM_IN: $enum_ident: ident $(;)? $({})?
 
 		TABLE: {
+			$( #[@ $table_serde: ident] )?
 			$( #[$table_meta: meta] )*
 			$table_vis: vis struct $table_ident: ident< $gen: ident >
 			$( where [ $( $bounds: tt )* ] )?
 			{
-			    $( $var_ident: ident ),*
+			    $(
+				    $var_ident: ident
+				    $( <[ $( $var_gen: tt )* ]> )?
+				    $( where [ $( $var_bound: tt )* ] )?
+			    ),*
 			    $(,)?
 		    }
 		}
+
+		$( KIND: $kind: ty; )?
+
+		$( OPS: $ops: tt )?
 	) => {
 		$crate::type_value_table! {
 			@TABLE_INTERNAL
 			$enum_ident
 
+			$( #[@ $table_serde] )?
 			$( #[$table_meta] )*
 			$table_vis struct $table_ident< $gen >
-			[$( $( $bounds )* )?]
+			[$( $( $bounds )* , )?]
+			[$( $kind )?]
+			[$( $ops )?]
 			$( where [ $( $bounds )* ] )?
 			{
-			    $( $var_ident ),*
+			    $( $var_ident [ $( $( $var_gen )* , )? ] [ $( $( $var_bound )* )? ] ),*
 		    }
 		}
 	};
 
 	// Table only
 	(
+		$( #[@ $table_serde: ident] )?
 		$( #[$table_meta: meta] )*
 		$table_vis: vis struct $table_ident: ident< $gen: ident >
 		$( where [ $( $bounds: tt )* ] )?
 		{
-		    $( $var_ident: ident ),*
+		    $(
+			    $var_ident: ident
+			    $( <[ $( $var_gen: tt )* ]> )?
+			    $( where [ $( $var_bound: tt )* ] )?
+		    ),*
 		    $(,)?
 	    }
+
+		$( KIND: $kind: ty; )?
+
+		$( OPS: $ops: tt )?
 	) => {
 		$crate::paste! {
 			$crate::type_value_table! {
 				@TABLE_INTERNAL
 				[<$table_ident Member>]
 
+				$( #[@ $table_serde] )?
 				$( #[$table_meta] )*
 				$table_vis struct $table_ident< $gen >
-				[$( $( $bounds )* )?]
+				[$( $( $bounds )* , )?]
+				[$( $kind )?]
+				[$( $ops )?]
 				$( where [ $( $bounds )* ] )?
 				{
-				    $( $var_ident ),*
+				    $( $var_ident [ $( $( $var_gen )* , )? ] [ $( $( $var_bound )* )? ] ),*
 			    }
 			}
 		}
@@ -189,23 +253,35 @@ macro_rules! type_value_table {
 		@TABLE_INTERNAL
 		$enum_ident: ident
 
+		$( #[@ $table_serde: ident] )?
 		$( #[$table_meta: meta] )*
 		$table_vis: vis struct $table_ident: ident < $gen: ident >
 		$token_bounds: tt
+		$kind_tt: tt
+		$ops_tt: tt
 		$( where [ $( $bounds: tt )* ] )?
 		{
-		    $( $var_ident: ident ),*
+		    $( $var_ident: ident $var_gen: tt $var_bound: tt ),*
 		    $(,)?
 	    }
 	) => {
 		$crate::paste! {
+			// The `where` bounds of the table are not placed on the struct, so that `map` and `zip` can produce
+			// values that don't satisfy them: only the combinators are available on such a table
 			$( #[$table_meta] )*
-		    $table_vis struct $table_ident<$gen>
-			$( where $( $bounds )*  )?
-			{
+		    $table_vis struct $table_ident<$gen> {
 		        $([<$var_ident:snake:lower>]: $gen),*
 		    }
 
+			$crate::serde_impls! {
+				[ $( $table_serde )? ]
+				@TABLE
+				$table_ident
+				{ $gen }
+				{ $( $( $bounds )* )? }
+				{ $( $var_ident => [<$var_ident:snake:lower>]: $gen ),* }
+			}
+
 			$table_vis enum [<$enum_ident Ref>]<'a, $gen>
 			$( where $( $bounds )*  )?
 			{
@@ -218,11 +294,22 @@ macro_rules! type_value_table {
 			    $($var_ident(&'a mut $gen)),*
 		    }
 
+			$crate::type_value_table! {
+				@KEYED $kind_tt
+				$table_ident<$gen>
+				{ $( $var_ident ),* }
+			}
+
+			$crate::value_table_ops! {
+				@SECTION $ops_tt
+				$table_ident { $( [<$var_ident:snake:lower>] ),* }
+			}
+
 		    $(
 			    $crate::type_value_table! {
 				    @MEMBER_OF_IMPL
 				    $gen;
-				    $var_ident;
+				    $var_ident $var_gen $var_bound;
 				    $table_ident;
 				    $token_bounds
 			    }
@@ -231,7 +318,7 @@ macro_rules! type_value_table {
 			impl<$gen> $table_ident<$gen>
 			$( where $( $bounds )*  )?
 			{
-				pub const LENGTH: usize = ${count($var_ident)};
+				pub const LENGTH: usize = $crate::count_idents!( $( $var_ident )* );
 
 				pub fn get<Member: $crate::MemberOf<Self>>(&self) -> &Member::MemberType {
 				    Member::get_in_table(self)
@@ -263,11 +350,26 @@ macro_rules! type_value_table {
 				}
 			}
 
+			// Like the struct, the combinators don't require the `where` bounds of the table
+			impl<$gen> $table_ident<$gen> {
+				pub fn zip<__U>(self, other: $table_ident<__U>) -> $table_ident<($gen, __U)> {
+					$table_ident {
+						$( [<$var_ident:snake:lower>]: (self.[<$var_ident:snake:lower>], other.[<$var_ident:snake:lower>]) ),*
+					}
+				}
+
+				pub fn as_ref(&self) -> $table_ident<&$gen> {
+					$table_ident {
+						$( [<$var_ident:snake:lower>]: &self.[<$var_ident:snake:lower>] ),*
+					}
+				}
+			}
+
 			impl<$gen> IntoIterator for $table_ident<$gen>
 			$( where $( $bounds )*  )?
 			{
 			    type Item = $gen;
-				type IntoIter = core::array::IntoIter<$gen, { $table_ident::<()>::LENGTH }>;
+				type IntoIter = core::array::IntoIter<$gen, { $crate::count_idents!( $( $var_ident )* ) }>;
 
 				fn into_iter(self) -> Self::IntoIter {
 					[ $( self.[<$var_ident:snake:lower>] ),* ].into_iter()
@@ -276,16 +378,51 @@ macro_rules! type_value_table {
 		}
 	};
 
-	// MemberOf impl
+	// `map`, `try_map` and `fold` pass the kind of each value, only generated with `KIND`.
+	// Like `zip`, they don't require the `where` bounds of the table.
+	(@KEYED [] $( $table: tt )*) => {};
+
+	(@KEYED [ $kind: ty ]
+		$table_ident: ident < $gen: ident >
+		{ $( $var_ident: ident ),* }
+	) => {
+		$crate::paste! {
+			impl<$gen> $table_ident<$gen> {
+				pub fn map<__U>(self, mut f: impl FnMut($kind, $gen) -> __U) -> $table_ident<__U> {
+					$table_ident {
+						$( [<$var_ident:snake:lower>]: f(<$kind>::$var_ident, self.[<$var_ident:snake:lower>]) ),*
+					}
+				}
+
+				/// Stops at the first error, in the order of the variants.
+				pub fn try_map<__U, __E>(
+					self,
+					mut f: impl FnMut($kind, $gen) -> Result<__U, __E>,
+				) -> Result<$table_ident<__U>, __E> {
+					Ok($table_ident {
+						$( [<$var_ident:snake:lower>]: f(<$kind>::$var_ident, self.[<$var_ident:snake:lower>])? ),*
+					})
+				}
+
+				pub fn fold<__B>(self, init: __B, mut f: impl FnMut(__B, $kind, $gen) -> __B) -> __B {
+					let acc = init;
+					$( let acc = f(acc, <$kind>::$var_ident, self.[<$var_ident:snake:lower>]); )*
+					acc
+				}
+			}
+		}
+	};
+
+	// MemberOf impl, the members are keyed by their own generics: `Gold<T>`
 	(@MEMBER_OF_IMPL
 		$gen: ident;
-		$var_ident: ident;
+		$var_ident: ident [ $( $var_gen: tt )* ] [ $( $var_bound: tt )* ];
 		$table_ident: ident;
 		[$( $bounds: tt )*]
 	) => {
 		$crate::paste! {
-			impl<$gen> $crate::MemberOf<$table_ident<$gen>> for $var_ident
-			where $( $bounds )*
+			impl<$( $var_gen )* $gen> $crate::MemberOf<$table_ident<$gen>> for $var_ident<$( $var_gen )*>
+			where $( $bounds )* $( $var_bound )*
 		    {
 			    type MemberType = $gen;
 
@@ -322,6 +459,10 @@ mod tests {
 			#[derive(Debug, Clone)] // Attributes to apply on the Table
 			pub struct DurationTable < Val >
 		}
+
+		KIND: {
+			pub enum DurationKind;
+		}
 	}
 
 	#[test]
@@ -337,6 +478,27 @@ mod tests {
 		assert_eq!(*hours_minutes, 3);
 		assert_eq!(*infinite, 4);
 	}
+
+	#[test]
+	fn test_combinators() {
+		let budget = DurationTable::new(10, 20, 30, 40);
+		let spent = DurationTable::new(1, 2, 3, 4);
+
+		let remaining = budget.zip(spent).map(|_, (budget, spent)| budget - spent);
+		assert_eq!(remaining.as_ref().map(|_, val| *val as u8).into_iter().collect::<Vec<_>>(), [9, 18, 27, 36]);
+
+		let total = remaining.clone().fold(0, |acc, key, val| match key {
+			DurationKind::Infinite => acc,
+			_ => acc + val,
+		});
+		assert_eq!(total, 54);
+
+		let checked = remaining.clone().try_map(|_, val| u8::try_from(val));
+		assert_eq!(*checked.unwrap().get::<HoursMinutes>(), 27_u8);
+
+		let failed = remaining.try_map(|key, val| if key == DurationKind::DaysSeconds { Err(key) } else { Ok(val) });
+		assert_eq!(failed.unwrap_err(), DurationKind::DaysSeconds);
+	}
 }
 
 #[allow(unused)]
@@ -371,6 +533,15 @@ mod tests_2 {
 				[fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error>]
 			}
 		}
+
+		OPS: { Add, AddAssign, Default }
+	}
+
+	#[test]
+	fn test_ops() {
+		let mut table = DurationTable::new(1, 2, 3, 4) + DurationTable::new(4, 3, 2, 1);
+		table += DurationTable::<i32>::default();
+		assert_eq!(table.into_iter().collect::<Vec<_>>(), [5; 4]);
 	}
 }
 
@@ -389,6 +560,14 @@ mod tests_3 {
 	struct HoursMinutes;
 	struct Infinite;
 
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub enum Unit {
+		Seconds,
+		DaysSeconds,
+		HoursMinutes,
+		Infinite,
+	}
+
 	type_value_table! {
 		// Name of the module that will contain all the generated code
 		#[derive(Debug, Clone)] // Attributes to apply on the Table
@@ -398,5 +577,71 @@ mod tests_3 {
 			HoursMinutes,
 			Infinite,
 		}
+
+		KIND: Unit;
+
+		OPS: { Sub, Neg }
+	}
+
+	#[test]
+	fn test_kind() {
+		let table = DurationTable::new(1, 2, 3, 4).map(|unit, val| (unit, -val));
+		assert_eq!(table.get::<Infinite>(), &(Unit::Infinite, -4));
+	}
+
+	// `String` isn't `Copy`, the intermediate table is only usable through the combinators
+	#[test]
+	fn test_map_out_of_bounds() {
+		let table = DurationTable::new(1, 2, 3, 4)
+			.map(|_, val| val.to_string())
+			.try_map(|_, val| val.parse::<i32>())
+			.unwrap();
+		assert_eq!(table.get::<HoursMinutes>(), &3);
+	}
+}
+
+#[allow(unused)]
+#[cfg(test)]
+mod test_generics {
+	use crate::type_value_table;
+
+	type_value_table! {
+		ENUM_OUT: {
+			#[vars(derive(Debug, Clone))]
+			pub enum Resource<['a, T]> where [T: Clone] {
+				Gold<[T]>(T) where [T: Clone],
+				Label<['a]>(&'a str),
+				Empty,
+			}
+		}
+
+		TABLE: {
+			#[derive(Debug, Clone)]
+			pub struct ResourceTable<Val> where [Val: Copy]
+		}
+	}
+
+	pub struct Stock<T>(T);
+	pub struct Workers;
+
+	type_value_table! {
+		pub struct StockTable<Val> {
+			Stock<[T]>,
+			Workers,
+		}
+	}
+
+	#[test]
+	fn test() {
+		let mut table = ResourceTable::new(5, 3, 1);
+		*table.get_mut::<Gold<String>>() += 1;
+
+		assert_eq!(table.get::<Gold<()>>(), &6);
+		assert_eq!(table.get::<Label>(), &3);
+		assert_eq!(table.get::<Empty>(), &1);
+
+		let stock = StockTable::new("stock", "workers");
+		assert_eq!(stock.get::<Stock<u8>>(), &"stock");
+		assert_eq!(stock.get::<Workers>(), &"workers");
 	}
 }
@@ -25,6 +25,8 @@ macro_rules! unit_enum_delegated {
 			    }
 		    )?
 	    }
+	    
+	    $( SET: $set: tt )?
     ) => {
 	    $( #[$enum_meta] )*
 	    $enum_vis enum $enum_ident {
@@ -61,6 +63,13 @@ macro_rules! unit_enum_delegated {
 			    )?   
 		    }
 	    }
+	    
+	    $crate::enum_variants_set! {
+		    SET [ $( $set )? ]
+		    $enum_ident {
+			    $( $var_ident ),*
+		    }
+	    }
     };
 }
 
@@ -98,12 +107,26 @@ mod test {
 				[fn test(&self);]
 			}
 		}
+		
+		SET: {
+			struct StateSet;
+		}
 	}
 
 	fn test(x: &mut StateEnum) {
 		x.tick(2.0);
 	}
 
+	#[test]
+	fn set() {
+		const ALL: StateSet = StateSet::from_array([StateEnum::Int, StateEnum::UInt]);
+
+		assert_eq!(ALL, StateSet::ALL);
+		assert_eq!(!StateSet::EMPTY.with(StateEnum::Int), StateSet::EMPTY.with(StateEnum::UInt));
+		assert_eq!(ALL.to_bits(), 0b11);
+		assert_eq!(format!("{:?}", ALL & StateSet::from_bits_truncate(0b10)), "{UInt}");
+	}
+
 	impl Tick for Int {
 		fn tick(&mut self, delta_time: f64) {
 			todo!()
@@ -0,0 +1,147 @@
+/// Implements element-wise operators for a table of values, used by the `OPS` section of
+/// [enum_variants_table!](crate::enum_variants_table) and [type_value_table!](crate::type_value_table).
+///
+/// Every impl is bounded on the values supporting the operator, so listing one never restricts the table.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! value_table_ops {
+	//------------------------------------------------------------------------------------------------------------------
+	// Opt-in through the `OPS` section, nothing is generated without it
+	(@SECTION [] $( $table: tt )*) => {};
+
+	(@SECTION [ { $( $op: ident ),* $(,)? } ] $table_ident: ident $fields_tt: tt) => {
+		$( $crate::value_table_ops! { @OP $op $table_ident $fields_tt } )*
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	// Operator name to its impl
+	(@OP Add $table_ident: ident $fields_tt: tt) => {
+		$crate::value_table_ops! { @BINARY [Add add] $table_ident $fields_tt }
+	};
+
+	(@OP Sub $table_ident: ident $fields_tt: tt) => {
+		$crate::value_table_ops! { @BINARY [Sub sub] $table_ident $fields_tt }
+	};
+
+	// Element-wise, and by a primitive scalar on either side: `table * 2.0`, `2.0 * table`
+	(@OP Mul $table_ident: ident $fields_tt: tt) => {
+		$crate::value_table_ops! { @BINARY [Mul mul] $table_ident $fields_tt }
+
+		$crate::value_table_ops! {
+			@SCALARS $table_ident $fields_tt
+			[i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64]
+		}
+	};
+
+	(@OP AddAssign $table_ident: ident { $( $field: ident ),* }) => {
+		impl<__T, __U> ::core::ops::AddAssign<$table_ident<__U>> for $table_ident<__T>
+		where __T: ::core::ops::AddAssign<__U>
+		{
+			fn add_assign(&mut self, rhs: $table_ident<__U>) {
+				$( ::core::ops::AddAssign::add_assign(&mut self.$field, rhs.$field); )*
+			}
+		}
+	};
+
+	(@OP Neg $table_ident: ident { $( $field: ident ),* }) => {
+		impl<__T> ::core::ops::Neg for $table_ident<__T>
+		where __T: ::core::ops::Neg
+		{
+			type Output = $table_ident<<__T as ::core::ops::Neg>::Output>;
+
+			fn neg(self) -> Self::Output {
+				$table_ident {
+					$( $field: ::core::ops::Neg::neg(self.$field) ),*
+				}
+			}
+		}
+	};
+
+	// Starts from `Default`, so that the sum of no tables is the default table
+	(@OP Sum $table_ident: ident $fields_tt: tt) => {
+		impl<__T> ::core::iter::Sum for $table_ident<__T>
+		where __T: ::core::ops::Add<Output = __T> + ::core::default::Default
+		{
+			fn sum<__I: Iterator<Item = Self>>(iter: __I) -> Self {
+				iter.fold(
+					$crate::value_table_ops!(@DEFAULT $table_ident $fields_tt),
+					|acc, table| $crate::value_table_ops!(@ADD acc table $table_ident $fields_tt),
+				)
+			}
+		}
+	};
+
+	(@OP Default $table_ident: ident $fields_tt: tt) => {
+		impl<__T> ::core::default::Default for $table_ident<__T>
+		where __T: ::core::default::Default
+		{
+			fn default() -> Self {
+				$crate::value_table_ops!(@DEFAULT $table_ident $fields_tt)
+			}
+		}
+	};
+
+	(@OP $op: ident $table_ident: ident $fields_tt: tt) => {
+		compile_error!(concat!(
+			"Unsupported table operator `", stringify!($op), "`.\n\
+			 Help: supported operators are `Add`, `Sub`, `Mul`, `AddAssign`, `Neg`, `Sum` and `Default`."
+		));
+	};
+
+	//------------------------------------------------------------------------------------------------------------------
+	(@BINARY [ $op: ident $op_fn: ident ] $table_ident: ident { $( $field: ident ),* }) => {
+		impl<__T, __U> ::core::ops::$op<$table_ident<__U>> for $table_ident<__T>
+		where __T: ::core::ops::$op<__U>
+		{
+			type Output = $table_ident<<__T as ::core::ops::$op<__U>>::Output>;
+
+			fn $op_fn(self, rhs: $table_ident<__U>) -> Self::Output {
+				$table_ident {
+					$( $field: ::core::ops::$op::$op_fn(self.$field, rhs.$field) ),*
+				}
+			}
+		}
+	};
+
+	(@SCALARS $table_ident: ident $fields_tt: tt [ $( $scalar: ident )* ]) => {
+		$( $crate::value_table_ops! { @SCALAR $scalar; $table_ident $fields_tt } )*
+	};
+
+	(@SCALAR $scalar: ident; $table_ident: ident { $( $field: ident ),* }) => {
+		impl<__T> ::core::ops::Mul<$scalar> for $table_ident<__T>
+		where __T: ::core::ops::Mul<$scalar>
+		{
+			type Output = $table_ident<<__T as ::core::ops::Mul<$scalar>>::Output>;
+
+			fn mul(self, rhs: $scalar) -> Self::Output {
+				$table_ident {
+					$( $field: self.$field * rhs ),*
+				}
+			}
+		}
+
+		impl<__T> ::core::ops::Mul<$table_ident<__T>> for $scalar
+		where $scalar: ::core::ops::Mul<__T>
+		{
+			type Output = $table_ident<<$scalar as ::core::ops::Mul<__T>>::Output>;
+
+			fn mul(self, rhs: $table_ident<__T>) -> Self::Output {
+				$table_ident {
+					$( $field: self * rhs.$field ),*
+				}
+			}
+		}
+	};
+
+	(@DEFAULT $table_ident: ident { $( $field: ident ),* }) => {
+		$table_ident {
+			$( $field: ::core::default::Default::default() ),*
+		}
+	};
+
+	(@ADD $lhs: ident $rhs: ident $table_ident: ident { $( $field: ident ),* }) => {
+		$table_ident {
+			$( $field: $lhs.$field + $rhs.$field ),*
+		}
+	};
+}
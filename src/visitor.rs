@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+
+/// How a visitor receives the variants, see the `VISITOR` section of [enum_visitor!](crate::enum_visitor).
+///
+/// `Out` is the type of the variant `T` as passed to the visitor's methods.
+pub trait Access<T> {
+	type Out;
+}
+
+/// The visitor receives the variants by value, through `accept`.
+pub struct ByValue;
+
+/// The visitor receives the variants by shared reference, through `accept_ref`.
+pub struct ByRef<'a>(PhantomData<&'a ()>);
+
+/// The visitor receives the variants by mutable reference, through `accept_mut`.
+pub struct ByMut<'a>(PhantomData<&'a mut ()>);
+
+impl<T> Access<T> for ByValue {
+	type Out = T;
+}
+
+impl<'a, T: 'a> Access<T> for ByRef<'a> {
+	type Out = &'a T;
+}
+
+impl<'a, T: 'a> Access<T> for ByMut<'a> {
+	type Out = &'a mut T;
+}
+
+/// Implemented by enums with a visitor, for `Enum`, `&Enum` and `&mut Enum`.
+///
+/// Calls `accept`, `accept_ref` or `accept_mut` depending on how the enum is passed,
+/// which is what lets [match_variant!](crate::match_variant) take any of them.
+pub trait Accept<V> {
+	type Output;
+
+	fn accept_visitor(self, visitor: &mut V) -> Self::Output;
+}
+
+/// Visitor built by `match_variant!`, each variant is handled by the closure that takes its type.
+///
+/// `H` is a list of closures `(F1, (F2, ()))`, `I` the position of each variant's closure in the list,
+/// inferred by the compiler, and `N` the number of closures, which must match the number of variants.
+#[doc(hidden)]
+pub struct Arms<R, H, I, const N: usize>(pub H, PhantomData<fn() -> (R, I)>);
+
+impl<R, H, I, const N: usize> Arms<R, H, I, N> {
+	pub fn new(handlers: H) -> Self { Self(handlers, PhantomData) }
+}
+
+/// Calls the closure of a list that takes `T`, `I` being its position.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+	message = "`match_variant!` has no arm for `{T}`",
+	label = "no closure takes `{T}`, or more than one does",
+	note = "`match_variant!` needs exactly one arm per variant"
+)]
+pub trait Handle<T, R, I> {
+	fn handle(&mut self, var: T) -> R;
+}
+
+#[doc(hidden)]
+pub struct Here;
+
+#[doc(hidden)]
+pub struct There<I>(PhantomData<I>);
+
+impl<T, R, F, Rest> Handle<T, R, Here> for (F, Rest)
+where F: FnMut(T) -> R
+{
+	fn handle(&mut self, var: T) -> R { (self.0)(var) }
+}
+
+impl<T, R, I, F, Rest> Handle<T, R, There<I>> for (F, Rest)
+where Rest: Handle<T, R, I>
+{
+	fn handle(&mut self, var: T) -> R { self.1.handle(var) }
+}